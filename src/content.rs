@@ -0,0 +1,131 @@
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::{
+    error::ContractError,
+    state::{Attachment, ContentRef},
+    Post,
+};
+
+const MAX_ATTACHMENTS: usize = 10;
+const ALLOWED_MIME_TYPES: &[&str] = &[
+    "image/png",
+    "image/jpeg",
+    "image/gif",
+    "image/webp",
+    "application/pdf",
+    "text/csv",
+    "audio/mpeg",
+    "video/mp4",
+];
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContentError {
+    #[error("post {id} keeps its content on-chain")]
+    NotOffChain { id: String },
+
+    #[error("expected {expected} bytes, got {actual}")]
+    SizeMismatch { expected: u64, actual: u64 },
+
+    #[error("expected sha256 {expected}, got {actual}")]
+    HashMismatch { expected: String, actual: String },
+}
+
+/// Checks that bytes fetched from a post's `content_ref` are the body the creator published,
+/// so readers don't have to trust the gateway they came from.
+pub fn verify_post_content(post: &Post, bytes: &[u8]) -> Result<(), ContentError> {
+    let content_ref = post
+        .content_ref
+        .as_ref()
+        .ok_or_else(|| ContentError::NotOffChain {
+            id: post.id.clone(),
+        })?;
+
+    let actual_size = bytes.len() as u64;
+    if actual_size != content_ref.size {
+        return Err(ContentError::SizeMismatch {
+            expected: content_ref.size,
+            actual: actual_size,
+        });
+    }
+
+    let actual_hash = sha256_hex(bytes);
+    if actual_hash != content_ref.sha256 {
+        return Err(ContentError::HashMismatch {
+            expected: content_ref.sha256.clone(),
+            actual: actual_hash,
+        });
+    }
+
+    Ok(())
+}
+
+/// Lowercase hex sha256 digest, in the format `ContentRef::sha256` is stored in.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+pub(crate) fn validate_content_ref(content_ref: &ContentRef) -> Result<(), ContractError> {
+    validate_uri(&content_ref.uri)?;
+    validate_sha256(&content_ref.sha256)?;
+    if content_ref.size == 0 {
+        return Err(ContractError::InvalidSize {
+            size: content_ref.size,
+        });
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_attachments(attachments: &[Attachment]) -> Result<(), ContractError> {
+    if attachments.len() > MAX_ATTACHMENTS {
+        return Err(ContractError::TooManyAttachments {
+            max: MAX_ATTACHMENTS,
+        });
+    }
+    for attachment in attachments {
+        if !ALLOWED_MIME_TYPES.contains(&attachment.mime_type.as_str()) {
+            return Err(ContractError::UnsupportedMimeType {
+                mime_type: attachment.mime_type.clone(),
+            });
+        }
+        validate_uri(&attachment.uri)?;
+        validate_sha256(&attachment.sha256)?;
+        if attachment.size == 0 {
+            return Err(ContractError::InvalidSize {
+                size: attachment.size,
+            });
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_uri(uri: &str) -> Result<(), ContractError> {
+    let valid = if let Some(cid) = uri.strip_prefix("ipfs://") {
+        // CIDv0 is base58 and CIDv1 is usually base32, both plain alphanumerics
+        !cid.is_empty() && cid.chars().all(|c| c.is_ascii_alphanumeric())
+    } else if let Some(rest) = uri.strip_prefix("https://") {
+        let host = rest.split('/').next().unwrap_or_default();
+        !host.is_empty() && !uri.chars().any(|c| c.is_whitespace() || c.is_control())
+    } else {
+        false
+    };
+
+    if !valid {
+        return Err(ContractError::InvalidUri {
+            uri: uri.to_string(),
+        });
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_sha256(hash: &str) -> Result<(), ContractError> {
+    if hash.len() != 64 || !hash.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')) {
+        return Err(ContractError::InvalidSha256 {
+            hash: hash.to_string(),
+        });
+    }
+    Ok(())
+}
@@ -1,148 +1,1620 @@
-use cosmwasm_std::{DepsMut, MessageInfo, Response, StdResult};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, StdResult};
 
-pub fn instantiate(_deps: DepsMut, _info: MessageInfo) -> StdResult<Response> {
+use crate::state::{Config, Limits, CONFIG};
+
+pub fn instantiate(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            admin: info.sender,
+            limits: Limits::default(),
+        },
+    )?;
     Ok(Response::new())
 }
 
-pub mod exec {
-    use cosmwasm_std::{Coin, DepsMut, Env, MessageInfo, Response};
+pub fn migrate(deps: DepsMut, env: Env) -> StdResult<Response> {
+    migration::move_posts_out_of_hubs(deps.storage)?;
+    migration::scope_likes_to_hubs(deps.storage)?;
+
+    if !CONFIG.exists(deps.storage) {
+        // Contracts from before the config hand it to their chain-level admin
+        let info = deps
+            .querier
+            .query_wasm_contract_info(env.contract.address)?;
+        let admin = deps
+            .api
+            .addr_validate(&info.admin.unwrap_or(info.creator))?;
+        CONFIG.save(
+            deps.storage,
+            &Config {
+                admin,
+                limits: Limits::default(),
+            },
+        )?;
+    }
+    migration::count_hub_posts(deps.storage)?;
+    migration::backfill_post_authors(deps.storage)?;
+    migration::index_hub_feeds(deps.storage)?;
+
+    Ok(Response::new().add_attribute("method", "migrate"))
+}
+
+mod migration {
+    use cosmwasm_std::{Addr, Order, StdResult, Storage};
+    use cw_storage_plus::Map;
+    use serde::{Deserialize, Serialize};
+
+    use crate::state::{
+        posts, Post, HUBS, HUB_FEED, LIKES, POST_COUNTS, POST_LIKERS, POST_SEQ, USER_LIKES,
+    };
+
+    /// The `posts` list hubs used to carry inline, newest first.
+    #[derive(Serialize, Deserialize)]
+    struct LegacyHubPosts {
+        #[serde(default)]
+        posts: Vec<Post>,
+    }
+
+    const LEGACY_HUBS: Map<&str, LegacyHubPosts> = Map::new("hubs");
+
+    pub fn move_posts_out_of_hubs(storage: &mut dyn Storage) -> StdResult<()> {
+        let hub_addrs: Vec<String> = HUBS
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?;
+
+        for hub_addr in hub_addrs {
+            let legacy = LEGACY_HUBS.load(storage, &hub_addr)?;
+            if legacy.posts.is_empty() {
+                continue;
+            }
+
+            let mut seq = POST_SEQ.may_load(storage, &hub_addr)?.unwrap_or_default();
+            for post in legacy.posts.into_iter().rev() {
+                posts().save(storage, (&hub_addr, seq), &post)?;
+                seq += 1;
+            }
+            POST_SEQ.save(storage, &hub_addr, &seq)?;
+
+            // Re-saving through the current type drops the inline posts
+            let hub = HUBS.load(storage, &hub_addr)?;
+            HUBS.save(storage, &hub_addr, &hub)?;
+        }
+
+        Ok(())
+    }
+
+    const LEGACY_LIKES: Map<&str, u64> = Map::new("likes");
+    const LEGACY_USER_LIKES: Map<(&Addr, &str), bool> = Map::new("user_likes");
+    const LEGACY_POST_LIKERS: Map<(&str, &Addr), bool> = Map::new("post_likers");
+
+    /// Hub of the most recently updated post with this id; only legacy ids can be shared by
+    /// more than one hub.
+    fn newest_hub_with_post(storage: &dyn Storage, post_id: &str) -> StdResult<Option<String>> {
+        let mut newest: Option<(u64, String)> = None;
+        for item in
+            posts()
+                .idx
+                .id
+                .prefix(post_id.to_string())
+                .range(storage, None, None, Order::Ascending)
+        {
+            let ((hub_addr, _), post) = item?;
+            if newest
+                .as_ref()
+                .is_none_or(|(updated, _)| post.updated > *updated)
+            {
+                newest = Some((post.updated, hub_addr));
+            }
+        }
+        Ok(newest.map(|(_, hub_addr)| hub_addr))
+    }
+
+    /// Moves likes keyed by post id alone under the hub of the newest post carrying that id,
+    /// so a shared legacy id doesn't multiply them. Likes on posts that no longer exist are
+    /// dropped.
+    pub fn scope_likes_to_hubs(storage: &mut dyn Storage) -> StdResult<()> {
+        let likes: Vec<(String, u64)> = LEGACY_LIKES
+            .range(storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?;
+        for (post_id, count) in likes {
+            if let Some(hub_addr) = newest_hub_with_post(storage, &post_id)? {
+                LIKES.save(storage, (&hub_addr, &post_id), &count)?;
+            }
+            LEGACY_LIKES.remove(storage, &post_id);
+        }
+
+        let user_likes: Vec<(Addr, String)> = LEGACY_USER_LIKES
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?;
+        for (user, post_id) in user_likes {
+            if let Some(hub_addr) = newest_hub_with_post(storage, &post_id)? {
+                USER_LIKES.save(storage, (&user, &hub_addr, &post_id), &true)?;
+                POST_LIKERS.save(storage, (&hub_addr, &post_id, &user), &true)?;
+            }
+            LEGACY_USER_LIKES.remove(storage, (&user, &post_id));
+        }
+
+        LEGACY_POST_LIKERS.clear(storage);
+
+        // Older posts sharing an id with a newer one start over from zero likes
+        let hub_posts: Vec<(String, String)> = posts()
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| item.map(|((hub_addr, _), post)| (hub_addr, post.id)))
+            .collect::<StdResult<_>>()?;
+        for (hub_addr, post_id) in hub_posts {
+            if !LIKES.has(storage, (&hub_addr, &post_id)) {
+                LIKES.save(storage, (&hub_addr, &post_id), &0u64)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn count_hub_posts(storage: &mut dyn Storage) -> StdResult<()> {
+        let hub_addrs: Vec<String> = HUBS
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?;
+        for hub_addr in hub_addrs {
+            let count = posts()
+                .prefix(&hub_addr)
+                .keys(storage, None, None, Order::Ascending)
+                .count();
+            POST_COUNTS.save(storage, &hub_addr, &(count as u64))?;
+        }
+        Ok(())
+    }
+
+    /// Posts from before guest authors were all written by their hub's creator.
+    pub fn backfill_post_authors(storage: &mut dyn Storage) -> StdResult<()> {
+        let unattributed: Vec<((String, u64), Post)> = posts()
+            .range(storage, None, None, Order::Ascending)
+            .filter(|item| match item {
+                Ok((_, post)) => post.author.is_none(),
+                Err(_) => true,
+            })
+            .collect::<StdResult<_>>()?;
+        for ((hub_addr, seq), mut post) in unattributed {
+            post.author = Some(Addr::unchecked(&hub_addr));
+            posts().save(storage, (&hub_addr, seq), &post)?;
+        }
+        Ok(())
+    }
+
+    /// Posts from before scheduling have a zero publish time, so they keep their order below
+    /// everything published since.
+    pub fn index_hub_feeds(storage: &mut dyn Storage) -> StdResult<()> {
+        let keys: Vec<(String, u64, u64)> = posts()
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| item.map(|((hub_addr, seq), post)| (hub_addr, post.published_at, seq)))
+            .collect::<StdResult<_>>()?;
+        for (hub_addr, published_at, seq) in keys {
+            HUB_FEED.save(storage, (&hub_addr, published_at, seq), &true)?;
+        }
+        Ok(())
+    }
+}
+
+pub mod exec {
+    use cosmwasm_std::{
+        to_json_binary, Addr, BankMsg, Binary, Coin, DepsMut, Env, MessageInfo, Order, Response,
+        StdResult, Storage,
+    };
+    use cw_storage_plus::Bound;
+
+    use crate::{
+        content::{validate_attachments, validate_content_ref},
+        error::ContractError,
+        msg::{CreatePostMsg, PostKey},
+        state::{
+            comment_replies, find_hub_post, find_post, posts, Comment, Hub, LengthLimit, Limits,
+            Poll, Post, PostRevision, PostVariant, PriceCurve, RepostRef, Series, SponsorPool,
+            Visibility, AUTHORS, COMMENTS, COMMENT_COUNTS, COMMENT_SEQ, COMMENT_THREADS, CONFIG,
+            CONTRIBUTIONS, ENCRYPTION_KEYS, EXPIRING_POSTS, HUBS, HUB_ADDRESS, HUB_FEED, HUB_TAGS,
+            LIKES, PINNED_POSTS, POLL_TALLIES, POLL_VOTES, POST_COUNTS, POST_KEYS, POST_LIKERS,
+            POST_REVISIONS, POST_SEQ, REPOST_COUNTS, SERIES, SERIES_SEQ, SPONSOR_POOLS,
+            SPONSOR_POOL_COUNT, SUBSCRIPTIONS, SUPPORTERS, TAGGED_POSTS, USER_LIKES,
+        },
+    };
+
+    const MAX_TAGS: usize = 5;
+    const MAX_TAG_LEN: usize = 32;
+    const MAX_PINNED_POSTS: usize = 3;
+    const MAX_SERIES_LEN: usize = 100;
+    const MAX_POLL_OPTIONS: usize = 10;
+    const MAX_VARIANTS: usize = 10;
+    const DEFAULT_PRUNE_LIMIT: u32 = 10;
+    const MAX_PRUNE_LIMIT: u32 = 30;
+
+    pub fn create_hub(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        hub_name: String,
+        need_pay: Coin,
+        pay_what_you_want: bool,
+        price_curve: Option<PriceCurve>,
+    ) -> Result<Response, ContractError> {
+        let sender_addr_str = info.sender.as_str().to_string();
+
+        // Check if creator already has a hub
+        if HUBS.load(deps.storage, &sender_addr_str).is_ok() {
+            return Err(ContractError::CreatorAlreadyHasHub);
+        }
+
+        let limits = CONFIG.load(deps.storage)?.limits;
+        check_text("hub_name", &hub_name, &limits.hub_name, &limits, false)?;
+
+        let new_hub = Hub {
+            creator: info.sender.clone(),
+            name: hub_name,
+            payment: need_pay,
+            subscribers: vec![info.sender.clone()],
+            pay_what_you_want,
+            price_curve,
+        };
+        HUBS.save(deps.storage, &sender_addr_str, &new_hub)?;
+
+        let mut hub_addresses = HUB_ADDRESS.load(deps.storage).unwrap_or_default();
+        hub_addresses.insert(0, sender_addr_str); // Insert the new address at the beginning of the vector
+        HUB_ADDRESS.save(deps.storage, &hub_addresses)?;
+
+        Ok(Response::new().add_attribute("method", "create_hub"))
+    }
+
+    pub fn subscribe_to_hub(
+        deps: DepsMut,
+        info: MessageInfo,
+        hub_addr: String,
+    ) -> Result<Response, ContractError> {
+        // Check if the hub exists
+        let hub = HUBS
+            .load(deps.storage, &hub_addr)
+            .map_err(|_| ContractError::HubNotFound)?;
+
+        let price = hub.subscription_price()?;
+        let sent_funds = info
+            .funds
+            .iter()
+            .find(|coin| coin.denom == price.denom)
+            .map(|coin| coin.amount)
+            .unwrap_or_default();
+
+        if !price.amount.is_zero() && sent_funds < price.amount {
+            return Err(ContractError::InsufficientFunds);
+        }
+
+        // Anything above the minimum of a pay-what-you-want hub is credited to the creator
+        let mut resp = Response::new().add_attribute("method", "subscribe_to_hub");
+        let contribution = sent_funds - price.amount.min(sent_funds);
+        if hub.pay_what_you_want && !contribution.is_zero() {
+            resp = resp.add_message(BankMsg::Send {
+                to_address: hub.creator.to_string(),
+                amount: vec![Coin::new(contribution.u128(), &price.denom)],
+            });
+            let previous = CONTRIBUTIONS
+                .may_load(deps.storage, (&hub_addr, &info.sender))?
+                .unwrap_or_default();
+            if !previous.is_zero() {
+                SUPPORTERS.remove(deps.storage, (&hub_addr, previous.u128(), &info.sender));
+            }
+            let total = previous + contribution;
+            CONTRIBUTIONS.save(deps.storage, (&hub_addr, &info.sender), &total)?;
+            SUPPORTERS.save(deps.storage, (&hub_addr, total.u128(), &info.sender), &true)?;
+        }
+
+        add_subscriber(deps.storage, &hub_addr, hub, info.sender)?;
+
+        Ok(resp)
+    }
+
+    fn add_subscriber(
+        storage: &mut dyn Storage,
+        hub_addr: &str,
+        mut hub: Hub,
+        user_addr: Addr,
+    ) -> Result<(), ContractError> {
+        // Check if the user is already subscribed
+        if SUBSCRIPTIONS.load(storage, (&user_addr, hub_addr)).is_ok() {
+            return Err(ContractError::AlreadySubscribed);
+        }
+
+        // Subscribe the user to the hub
+        SUBSCRIPTIONS.save(storage, (&user_addr, hub_addr), &true)?;
+
+        // Optionally, add the user to the hub's subscribers list
+        // This step depends on whether you want to maintain a list of subscribers in the Hub struct
+        hub.subscribers.push(user_addr);
+        HUBS.save(storage, hub_addr, &hub)?;
+
+        Ok(())
+    }
+
+    pub fn add_author(
+        deps: DepsMut,
+        info: MessageInfo,
+        author: Addr,
+    ) -> Result<Response, ContractError> {
+        let hub_addr = info.sender.as_str();
+        if !HUBS.has(deps.storage, hub_addr) {
+            return Err(ContractError::HubNotFound {});
+        }
+
+        let author = deps.api.addr_validate(author.as_str())?;
+        AUTHORS.save(deps.storage, (hub_addr, &author), &true)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "add_author")
+            .add_attribute("author", author))
+    }
+
+    pub fn remove_author(
+        deps: DepsMut,
+        info: MessageInfo,
+        author: Addr,
+    ) -> Result<Response, ContractError> {
+        let hub_addr = info.sender.as_str();
+        if !AUTHORS.has(deps.storage, (hub_addr, &author)) {
+            return Err(ContractError::NotAnAuthor {
+                addr: author.into_string(),
+            });
+        }
+
+        AUTHORS.remove(deps.storage, (hub_addr, &author));
+
+        Ok(Response::new()
+            .add_attribute("method", "remove_author")
+            .add_attribute("author", author))
+    }
+
+    pub fn create_post(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: CreatePostMsg,
+    ) -> Result<Response, ContractError> {
+        let CreatePostMsg {
+            post_id,
+            title,
+            content,
+            publish_at,
+            summary,
+            content_ref,
+            encrypted,
+            tags,
+            attachments,
+            visibility,
+            poll,
+            hub_addr,
+            expires_at,
+            lang,
+            variants,
+        } = msg;
+        let hub_addr = hub_addr.unwrap_or_else(|| info.sender.clone());
+        let hub_id = hub_addr.as_str();
+
+        // Check if the hub exists before proceeding
+        if !HUBS.has(deps.storage, hub_id) {
+            return Err(ContractError::HubNotFound {});
+        }
+
+        // Besides the creator, only authors they invited may publish into a hub
+        let is_creator = hub_addr == info.sender;
+        if !is_creator && !AUTHORS.has(deps.storage, (hub_id, &info.sender)) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let limits = CONFIG.load(deps.storage)?.limits;
+        check_text("title", &title, &limits.title, &limits, false)?;
+        check_text("summary", &summary, &limits.summary, &limits, true)?;
+        check_text("content", &content, &limits.content, &limits, true)?;
+        count_new_post(deps.storage, hub_id, &limits)?;
+
+        let seq = POST_SEQ.may_load(deps.storage, hub_id)?.unwrap_or_default();
+
+        // `/` is reserved for generated ids, so a chosen id can never squat on one
+        let post_id = match post_id {
+            Some(id) if id.is_empty() || id.contains('/') => {
+                return Err(ContractError::InvalidPostId { id })
+            }
+            Some(id) => id,
+            None => format!("{}/{}", hub_id, seq),
+        };
+        if find_post(deps.storage, &post_id)?.is_some() {
+            return Err(ContractError::PostAlreadyExists { id: post_id });
+        }
+
+        if matches!(publish_at, Some(publish_at) if publish_at <= env.block.time.seconds()) {
+            return Err(ContractError::InvalidPublishTime);
+        }
+        // An expiry must leave the post some time to be seen
+        let visible_from = publish_at.unwrap_or(env.block.time.seconds());
+        if matches!(expires_at, Some(expires_at) if expires_at <= visible_from) {
+            return Err(ContractError::InvalidExpiry);
+        }
+
+        if let Some(content_ref) = &content_ref {
+            // The digest is of the off-chain body, so an inline one would contradict it
+            if !content.is_empty() {
+                return Err(ContractError::ContentRefWithContent);
+            }
+            validate_content_ref(content_ref)?;
+        }
+        validate_attachments(&attachments)?;
+        validate_variants(lang.as_deref(), &variants, &limits)?;
+        if let Some(poll) = &poll {
+            validate_poll(poll, env.block.time.seconds(), &limits)?;
+        }
+
+        let tags = normalize_tags(tags)?;
+
+        let post = Post {
+            id: post_id.clone(),
+            title,
+            summary,
+            content,
+            updated: env.block.time.seconds(),
+            unpublished: false,
+            publish_at,
+            published_at: visible_from,
+            content_ref,
+            encrypted,
+            tags,
+            repost_of: None,
+            attachments,
+            visibility,
+            poll,
+            author: Some(info.sender.clone()),
+            expires_at,
+            lang,
+            variants,
+        };
+
+        posts().save(deps.storage, (hub_id, seq), &post)?;
+        HUB_FEED.save(deps.storage, (hub_id, post.published_at, seq), &true)?;
+        if let Some(expires_at) = expires_at {
+            EXPIRING_POSTS.save(deps.storage, (hub_id, expires_at, seq), &true)?;
+        }
+        POST_SEQ.save(deps.storage, hub_id, &(seq + 1))?;
+
+        for tag in &post.tags {
+            TAGGED_POSTS.save(deps.storage, (tag, hub_id, seq), &true)?;
+            HUB_TAGS.update(deps.storage, (hub_id, tag), |count| -> StdResult<_> {
+                Ok(count.unwrap_or_default() + 1)
+            })?;
+        }
+
+        LIKES.save(deps.storage, (hub_id, &post_id), &0u64)?;
+        if is_creator {
+            SUBSCRIPTIONS.save(deps.storage, (&info.sender, hub_id), &true)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("method", "create_post")
+            .add_attribute("post_id", &post_id)
+            .set_data(to_json_binary(&post_id)?))
+    }
+
+    pub fn repost(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        source_hub: String,
+        source_post_id: String,
+        commentary: Option<String>,
+    ) -> Result<Response, ContractError> {
+        let hub_id = info.sender.as_str();
+        if !HUBS.has(deps.storage, hub_id) {
+            return Err(ContractError::HubNotFound {});
+        }
+
+        let (_, source) = find_hub_post(deps.storage, &source_hub, &source_post_id)?
+            .ok_or(ContractError::PostNotFound)?;
+        if !source.is_live(env.block.time.seconds()) {
+            return Err(ContractError::PostNotFound);
+        }
+
+        let limits = CONFIG.load(deps.storage)?.limits;
+        if let Some(commentary) = &commentary {
+            // Stored as the repost's summary, so it is held to the same limit
+            check_text("summary", commentary, &limits.summary, &limits, true)?;
+        }
+        count_new_post(deps.storage, hub_id, &limits)?;
+
+        // Reposting a repost amplifies the original
+        let repost_of = source.repost_of.unwrap_or(RepostRef {
+            hub_addr: source_hub,
+            post_id: source_post_id,
+        });
+
+        let seq = POST_SEQ.may_load(deps.storage, hub_id)?.unwrap_or_default();
+        let post_id = format!("{}/{}", hub_id, seq);
+        let post = Post {
+            id: post_id.clone(),
+            title: String::new(),
+            summary: commentary.unwrap_or_default(),
+            content: String::new(),
+            updated: env.block.time.seconds(),
+            unpublished: false,
+            publish_at: None,
+            published_at: env.block.time.seconds(),
+            content_ref: None,
+            encrypted: false,
+            tags: vec![],
+            repost_of: Some(repost_of.clone()),
+            attachments: vec![],
+            // Only the commentary is the reposter's own, and that is public anyway
+            visibility: Visibility::Public,
+            poll: None,
+            author: Some(info.sender.clone()),
+            expires_at: None,
+            lang: None,
+            variants: vec![],
+        };
+
+        posts().save(deps.storage, (hub_id, seq), &post)?;
+        HUB_FEED.save(deps.storage, (hub_id, post.published_at, seq), &true)?;
+        POST_SEQ.save(deps.storage, hub_id, &(seq + 1))?;
+        LIKES.save(deps.storage, (hub_id, &post_id), &0u64)?;
+        SUBSCRIPTIONS.save(deps.storage, (&info.sender, hub_id), &true)?;
+
+        REPOST_COUNTS.update(
+            deps.storage,
+            (&repost_of.hub_addr, &repost_of.post_id),
+            |count| -> StdResult<_> { Ok(count.unwrap_or_default() + 1) },
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("method", "repost")
+            .add_attribute("post_id", &post_id)
+            .set_data(to_json_binary(&post_id)?))
+    }
+
+    fn check_text(
+        field: &str,
+        value: &str,
+        limit: &LengthLimit,
+        limits: &Limits,
+        multiline: bool,
+    ) -> Result<(), ContractError> {
+        let len = value.chars().count() as u32;
+        if len < limit.min || len > limit.max {
+            return Err(ContractError::InvalidLength {
+                field: field.to_string(),
+                min: limit.min,
+                max: limit.max,
+            });
+        }
+
+        let forbidden = |c: char| c.is_control() && !(multiline && matches!(c, '\n' | '\r' | '\t'));
+        if limits.forbid_control_chars && value.chars().any(forbidden) {
+            return Err(ContractError::ControlCharacter {
+                field: field.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn count_new_post(
+        storage: &mut dyn Storage,
+        hub_addr: &str,
+        limits: &Limits,
+    ) -> Result<(), ContractError> {
+        let count = POST_COUNTS.may_load(storage, hub_addr)?.unwrap_or_default();
+        if count >= limits.max_posts_per_hub {
+            return Err(ContractError::TooManyPosts {
+                max: limits.max_posts_per_hub,
+            });
+        }
+        POST_COUNTS.save(storage, hub_addr, &(count + 1))?;
+        Ok(())
+    }
+
+    fn validate_poll(poll: &Poll, now: u64, limits: &Limits) -> Result<(), ContractError> {
+        let options_valid = (2..=MAX_POLL_OPTIONS).contains(&poll.options.len())
+            && poll.options.iter().all(|option| !option.trim().is_empty());
+        if poll.question.trim().is_empty() || !options_valid || poll.closes_at <= now {
+            return Err(ContractError::InvalidPoll);
+        }
+
+        // Questions and options are shown like titles
+        check_text(
+            "poll_question",
+            &poll.question,
+            &limits.title,
+            limits,
+            false,
+        )?;
+        for option in &poll.options {
+            check_text("poll_option", option, &limits.title, limits, false)?;
+        }
+        Ok(())
+    }
+
+    fn validate_variants(
+        lang: Option<&str>,
+        variants: &[PostVariant],
+        limits: &Limits,
+    ) -> Result<(), ContractError> {
+        let Some(lang) = lang else {
+            if variants.is_empty() {
+                return Ok(());
+            }
+            return Err(ContractError::MissingDefaultLanguage);
+        };
+        if variants.len() > MAX_VARIANTS {
+            return Err(ContractError::TooManyVariants { max: MAX_VARIANTS });
+        }
+
+        let mut seen = vec![lang.to_ascii_lowercase()];
+        validate_language_tag(lang)?;
+        for variant in variants {
+            validate_language_tag(&variant.lang)?;
+            let normalized = variant.lang.to_ascii_lowercase();
+            if seen.contains(&normalized) {
+                return Err(ContractError::DuplicateLanguage {
+                    lang: variant.lang.clone(),
+                });
+            }
+            seen.push(normalized);
+            check_text("title", &variant.title, &limits.title, limits, false)?;
+            check_text("content", &variant.content, &limits.content, limits, true)?;
+        }
+        Ok(())
+    }
+
+    /// Checks the shape of a BCP-47 tag: a 2-8 letter language subtag, then 1-8 character
+    /// alphanumeric subtags.
+    fn validate_language_tag(tag: &str) -> Result<(), ContractError> {
+        let mut subtags = tag.split('-');
+        let language = subtags.next().unwrap_or_default();
+        let valid = (2..=8).contains(&language.len())
+            && language.chars().all(|c| c.is_ascii_alphabetic())
+            && subtags.all(|subtag| {
+                (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+            });
+        if !valid {
+            return Err(ContractError::InvalidLanguageTag {
+                tag: tag.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Lowercases and trims tags, dropping duplicates, and checks them against the tag rules.
+    fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>, ContractError> {
+        let mut normalized: Vec<String> = vec![];
+        for tag in tags {
+            let tag = tag.trim().to_lowercase();
+            if tag.is_empty() || tag.len() > MAX_TAG_LEN {
+                return Err(ContractError::InvalidTagLength {
+                    tag,
+                    min: 1,
+                    max: MAX_TAG_LEN,
+                });
+            }
+            if !tag
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+            {
+                return Err(ContractError::InvalidTagCharacter { tag });
+            }
+            if !normalized.contains(&tag) {
+                normalized.push(tag);
+            }
+        }
+
+        if normalized.len() > MAX_TAGS {
+            return Err(ContractError::TooManyTags { max: MAX_TAGS });
+        }
+
+        Ok(normalized)
+    }
+
+    pub fn vote_poll(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        hub_addr: String,
+        post_id: String,
+        option: u32,
+    ) -> Result<Response, ContractError> {
+        let now = env.block.time.seconds();
+        let (_, post) =
+            find_hub_post(deps.storage, &hub_addr, &post_id)?.ok_or(ContractError::PostNotFound)?;
+        if !post.is_live(now) {
+            return Err(ContractError::PostNotFound);
+        }
+        let poll = post.poll.ok_or(ContractError::PollNotFound {
+            id: post_id.clone(),
+        })?;
+
+        if !SUBSCRIPTIONS.has(deps.storage, (&info.sender, &hub_addr)) {
+            return Err(ContractError::NotSubscribed {
+                addr: info.sender.into_string(),
+            });
+        }
+        if poll.is_closed(now) {
+            return Err(ContractError::PollClosed { id: post_id });
+        }
+        if option as usize >= poll.options.len() {
+            return Err(ContractError::InvalidPollOption { option });
+        }
+        if POLL_VOTES.has(deps.storage, (&hub_addr, &post_id, &info.sender)) {
+            return Err(ContractError::AlreadyVoted { id: post_id });
+        }
+
+        POLL_VOTES.save(deps.storage, (&hub_addr, &post_id, &info.sender), &option)?;
+        POLL_TALLIES.update(
+            deps.storage,
+            (&hub_addr, &post_id, option),
+            |votes| -> StdResult<_> { Ok(votes.unwrap_or_default() + 1) },
+        )?;
+
+        Ok(Response::new().add_attribute("method", "vote_poll"))
+    }
+
+    pub fn like_post(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        hub_addr: String,
+        post_id: String,
+    ) -> Result<Response, ContractError> {
+        match find_hub_post(deps.storage, &hub_addr, &post_id)? {
+            Some((_, post)) if post.is_live(env.block.time.seconds()) => {}
+            _ => return Err(ContractError::PostNotFound),
+        }
+
+        if USER_LIKES.has(deps.storage, (&info.sender, &hub_addr, &post_id)) {
+            return Err(ContractError::PostAlreadyLiked { id: post_id });
+        }
+
+        let likes = LIKES
+            .may_load(deps.storage, (&hub_addr, &post_id))?
+            .unwrap_or_default();
+        LIKES.save(deps.storage, (&hub_addr, &post_id), &(likes + 1))?;
+
+        USER_LIKES.save(deps.storage, (&info.sender, &hub_addr, &post_id), &true)?;
+        POST_LIKERS.save(deps.storage, (&hub_addr, &post_id, &info.sender), &true)?;
+
+        Ok(Response::new().add_attribute("method", "like_post"))
+    }
+
+    /// Loads a post for an operation only its hub creator may perform.
+    fn load_own_post(
+        storage: &dyn Storage,
+        sender: &Addr,
+        post_id: &str,
+    ) -> Result<((String, u64), Post), ContractError> {
+        match find_hub_post(storage, sender.as_str(), post_id)? {
+            Some((seq, post)) => Ok(((sender.to_string(), seq), post)),
+            // Only in someone else's hub
+            None if find_post(storage, post_id)?.is_some() => Err(ContractError::Unauthorized),
+            None => Err(ContractError::PostNotFound),
+        }
+    }
+
+    pub fn unlike_post(
+        deps: DepsMut,
+        info: MessageInfo,
+        hub_addr: String,
+        post_id: String,
+    ) -> Result<Response, ContractError> {
+        if !USER_LIKES.has(deps.storage, (&info.sender, &hub_addr, &post_id)) {
+            return Err(ContractError::PostNotLiked { id: post_id });
+        }
+
+        let likes = LIKES
+            .may_load(deps.storage, (&hub_addr, &post_id))?
+            .unwrap_or_default();
+        LIKES.save(
+            deps.storage,
+            (&hub_addr, &post_id),
+            &likes.saturating_sub(1),
+        )?;
+
+        USER_LIKES.remove(deps.storage, (&info.sender, &hub_addr, &post_id));
+        POST_LIKERS.remove(deps.storage, (&hub_addr, &post_id, &info.sender));
+
+        Ok(Response::new().add_attribute("method", "unlike_post"))
+    }
+
+    pub fn delete_post(
+        deps: DepsMut,
+        info: MessageInfo,
+        post_id: String,
+    ) -> Result<Response, ContractError> {
+        let ((hub_addr, seq), post) = load_own_post(deps.storage, &info.sender, &post_id)?;
+
+        remove_post(deps.storage, &hub_addr, seq, &post)?;
+
+        Ok(Response::new().add_attribute("method", "delete_post"))
+    }
+
+    /// Removes a post together with everything stored against it.
+    fn remove_post(
+        storage: &mut dyn Storage,
+        hub_addr: &str,
+        seq: u64,
+        post: &Post,
+    ) -> Result<(), ContractError> {
+        let post_id = post.id.as_str();
+        posts().remove(storage, (hub_addr, seq))?;
+        HUB_FEED.remove(storage, (hub_addr, post.published_at, seq));
+        POST_COUNTS.update(storage, hub_addr, |count| -> StdResult<_> {
+            Ok(count.unwrap_or_default().saturating_sub(1))
+        })?;
+        if let Some(mut pinned) = PINNED_POSTS.may_load(storage, hub_addr)? {
+            pinned.retain(|pinned_seq| *pinned_seq != seq);
+            PINNED_POSTS.save(storage, hub_addr, &pinned)?;
+        }
+
+        let series: Vec<Series> = SERIES
+            .prefix(hub_addr)
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, series)| series))
+            .filter(|item| match item {
+                Ok(series) => series.post_ids.iter().any(|id| id == post_id),
+                Err(_) => true,
+            })
+            .collect::<StdResult<_>>()?;
+        for mut series in series {
+            series.post_ids.retain(|id| id != post_id);
+            SERIES.save(storage, (hub_addr, series.id), &series)?;
+        }
+        LIKES.remove(storage, (hub_addr, post_id));
+
+        let likers: Vec<Addr> = POST_LIKERS
+            .prefix((hub_addr, post_id))
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?;
+        for liker in likers {
+            USER_LIKES.remove(storage, (&liker, hub_addr, post_id));
+            POST_LIKERS.remove(storage, (hub_addr, post_id, &liker));
+        }
+
+        POST_REVISIONS
+            .prefix((hub_addr, post_id))
+            .clear(storage, None);
+        POST_KEYS.prefix((hub_addr, post_id)).clear(storage, None);
+        COMMENTS.prefix((hub_addr, post_id)).clear(storage, None);
+        COMMENT_THREADS
+            .prefix((hub_addr, post_id))
+            .clear(storage, None);
+        COMMENT_SEQ.remove(storage, (hub_addr, post_id));
+        COMMENT_COUNTS.remove(storage, (hub_addr, post_id));
+        if let Some(expires_at) = post.expires_at {
+            EXPIRING_POSTS.remove(storage, (hub_addr, expires_at, seq));
+        }
+        POLL_VOTES.prefix((hub_addr, post_id)).clear(storage, None);
+        POLL_TALLIES
+            .prefix((hub_addr, post_id))
+            .clear(storage, None);
+        REPOST_COUNTS.remove(storage, (hub_addr, post_id));
+
+        if let Some(repost_of) = &post.repost_of {
+            let key = (repost_of.hub_addr.as_str(), repost_of.post_id.as_str());
+            if let Some(count) = REPOST_COUNTS.may_load(storage, key)? {
+                REPOST_COUNTS.save(storage, key, &count.saturating_sub(1))?;
+            }
+        }
+
+        for tag in &post.tags {
+            TAGGED_POSTS.remove(storage, (tag, hub_addr, seq));
+            let still_tagged = TAGGED_POSTS
+                .prefix((tag, hub_addr))
+                .keys(storage, None, None, Order::Ascending)
+                .next()
+                .is_some();
+            if !still_tagged {
+                HUB_TAGS.remove(storage, (hub_addr, tag));
+            } else if !post.unpublished {
+                let count = HUB_TAGS.load(storage, (hub_addr, tag))?;
+                HUB_TAGS.save(storage, (hub_addr, tag), &count.saturating_sub(1))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn prune_expired_posts(
+        deps: DepsMut,
+        env: Env,
+        hub_addr: String,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        let limit = limit.unwrap_or(DEFAULT_PRUNE_LIMIT).min(MAX_PRUNE_LIMIT) as usize;
+        let now = env.block.time.seconds();
+
+        let expired: Vec<(u64, u64)> = EXPIRING_POSTS
+            .sub_prefix(&hub_addr)
+            .keys(
+                deps.storage,
+                None,
+                Some(Bound::inclusive((now, u64::MAX))),
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect::<StdResult<_>>()?;
+        for (_, seq) in &expired {
+            let post = posts().load(deps.storage, (&hub_addr, *seq))?;
+            remove_post(deps.storage, &hub_addr, *seq, &post)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("method", "prune_expired_posts")
+            .add_attribute("pruned", expired.len().to_string()))
+    }
+
+    pub fn reschedule_post(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        post_id: String,
+        publish_at: u64,
+    ) -> Result<Response, ContractError> {
+        let ((hub_addr, seq), mut post) = load_own_post(deps.storage, &info.sender, &post_id)?;
+
+        let now = env.block.time.seconds();
+        if !post.is_scheduled(now) {
+            return Err(ContractError::PostNotScheduled { id: post_id });
+        }
+        if publish_at <= now
+            || matches!(post.expires_at, Some(expires_at) if expires_at <= publish_at)
+        {
+            return Err(ContractError::InvalidPublishTime);
+        }
+
+        HUB_FEED.remove(deps.storage, (&hub_addr, post.published_at, seq));
+        post.publish_at = Some(publish_at);
+        post.published_at = publish_at;
+        posts().save(deps.storage, (&hub_addr, seq), &post)?;
+        HUB_FEED.save(deps.storage, (&hub_addr, publish_at, seq), &true)?;
+
+        Ok(Response::new().add_attribute("method", "reschedule_post"))
+    }
+
+    pub fn cancel_scheduled_post(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        post_id: String,
+    ) -> Result<Response, ContractError> {
+        let ((hub_addr, seq), post) = load_own_post(deps.storage, &info.sender, &post_id)?;
+
+        if !post.is_scheduled(env.block.time.seconds()) {
+            return Err(ContractError::PostNotScheduled { id: post_id });
+        }
+
+        remove_post(deps.storage, &hub_addr, seq, &post)?;
+
+        Ok(Response::new().add_attribute("method", "cancel_scheduled_post"))
+    }
+
+    pub fn comment(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        hub_addr: String,
+        post_id: String,
+        parent_id: Option<u64>,
+        body: String,
+    ) -> Result<Response, ContractError> {
+        let (_, post) =
+            find_hub_post(deps.storage, &hub_addr, &post_id)?.ok_or(ContractError::PostNotFound)?;
+        if !post.is_live(env.block.time.seconds()) {
+            return Err(ContractError::PostNotFound);
+        }
+
+        if !SUBSCRIPTIONS.has(deps.storage, (&info.sender, &hub_addr)) {
+            return Err(ContractError::NotSubscribed {
+                addr: info.sender.into_string(),
+            });
+        }
+
+        let limits = CONFIG.load(deps.storage)?.limits;
+        check_text("comment", &body, &limits.comment, &limits, true)?;
+        // Whitespace alone doesn't make a comment either
+        if body.trim().is_empty() {
+            return Err(ContractError::InvalidLength {
+                field: "comment".to_string(),
+                min: limits.comment.min.max(1),
+                max: limits.comment.max,
+            });
+        }
+
+        if let Some(parent_id) = parent_id {
+            if !COMMENTS.has(deps.storage, (&hub_addr, &post_id, parent_id)) {
+                return Err(ContractError::CommentNotFound { id: parent_id });
+            }
+        }
+
+        let key = (hub_addr.as_str(), post_id.as_str());
+        let id = COMMENT_SEQ.may_load(deps.storage, key)?.unwrap_or(1);
+        COMMENT_SEQ.save(deps.storage, key, &(id + 1))?;
+        COMMENT_COUNTS.update(deps.storage, key, |count| -> StdResult<_> {
+            Ok(count.unwrap_or_default() + 1)
+        })?;
+
+        let comment = Comment {
+            id,
+            author: info.sender,
+            parent_id,
+            body,
+            created: env.block.time.seconds(),
+            hidden: false,
+        };
+        COMMENTS.save(deps.storage, (&hub_addr, &post_id, id), &comment)?;
+        COMMENT_THREADS.save(
+            deps.storage,
+            (&hub_addr, &post_id, (parent_id.unwrap_or_default(), id)),
+            &true,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("method", "comment")
+            .add_attribute("comment_id", id.to_string())
+            .set_data(to_json_binary(&id)?))
+    }
+
+    pub fn hide_comment(
+        deps: DepsMut,
+        info: MessageInfo,
+        post_id: String,
+        comment_id: u64,
+    ) -> Result<Response, ContractError> {
+        let ((hub_addr, _), _) = load_own_post(deps.storage, &info.sender, &post_id)?;
+
+        let mut comment = COMMENTS
+            .may_load(deps.storage, (&hub_addr, &post_id, comment_id))?
+            .ok_or(ContractError::CommentNotFound { id: comment_id })?;
+        comment.hidden = true;
+        COMMENTS.save(deps.storage, (&hub_addr, &post_id, comment_id), &comment)?;
+
+        Ok(Response::new().add_attribute("method", "hide_comment"))
+    }
+
+    pub fn delete_comment(
+        deps: DepsMut,
+        info: MessageInfo,
+        post_id: String,
+        comment_id: u64,
+    ) -> Result<Response, ContractError> {
+        let ((hub_addr, _), _) = load_own_post(deps.storage, &info.sender, &post_id)?;
+
+        let comment = COMMENTS
+            .may_load(deps.storage, (&hub_addr, &post_id, comment_id))?
+            .ok_or(ContractError::CommentNotFound { id: comment_id })?;
+
+        // Walk the subtree through the replies index, parents before their replies
+        let mut removed = vec![(comment.parent_id.unwrap_or_default(), comment_id)];
+        let mut next = 0;
+        while let Some(&(_, parent_id)) = removed.get(next) {
+            for id in comment_replies(deps.storage, &hub_addr, &post_id, parent_id, None) {
+                removed.push((parent_id, id?));
+            }
+            next += 1;
+        }
+
+        for (parent_id, id) in &removed {
+            COMMENTS.remove(deps.storage, (&hub_addr, &post_id, *id));
+            COMMENT_THREADS.remove(deps.storage, (&hub_addr, &post_id, (*parent_id, *id)));
+        }
+        COMMENT_COUNTS.update(
+            deps.storage,
+            (&hub_addr, &post_id),
+            |count| -> StdResult<_> {
+                Ok(count
+                    .unwrap_or_default()
+                    .saturating_sub(removed.len() as u64))
+            },
+        )?;
+
+        Ok(Response::new().add_attribute("method", "delete_comment"))
+    }
+
+    pub fn register_encryption_key(
+        deps: DepsMut,
+        info: MessageInfo,
+        public_key: Binary,
+    ) -> Result<Response, ContractError> {
+        // X25519 public keys are always 32 bytes
+        if public_key.len() != 32 {
+            return Err(ContractError::InvalidEncryptionKey);
+        }
+
+        ENCRYPTION_KEYS.save(deps.storage, &info.sender, &public_key)?;
+
+        Ok(Response::new().add_attribute("method", "register_encryption_key"))
+    }
+
+    pub fn publish_post_keys(
+        deps: DepsMut,
+        info: MessageInfo,
+        post_id: String,
+        keys: Vec<PostKey>,
+    ) -> Result<Response, ContractError> {
+        let ((hub_addr, _), post) = load_own_post(deps.storage, &info.sender, &post_id)?;
+
+        if !post.encrypted {
+            return Err(ContractError::PostNotEncrypted { id: post_id });
+        }
+
+        for PostKey {
+            subscriber,
+            envelope,
+        } in keys
+        {
+            if !SUBSCRIPTIONS.has(deps.storage, (&subscriber, &hub_addr)) {
+                return Err(ContractError::NotSubscribed {
+                    addr: subscriber.into_string(),
+                });
+            }
+            POST_KEYS.save(deps.storage, (&hub_addr, &post_id, &subscriber), &envelope)?;
+        }
+
+        Ok(Response::new().add_attribute("method", "publish_post_keys"))
+    }
+
+    pub fn pin_post(
+        deps: DepsMut,
+        info: MessageInfo,
+        post_id: String,
+    ) -> Result<Response, ContractError> {
+        let ((hub_addr, seq), _) = load_own_post(deps.storage, &info.sender, &post_id)?;
+
+        let mut pinned = PINNED_POSTS
+            .may_load(deps.storage, &hub_addr)?
+            .unwrap_or_default();
+        if pinned.contains(&seq) {
+            return Err(ContractError::PostAlreadyPinned { id: post_id });
+        }
+        if pinned.len() >= MAX_PINNED_POSTS {
+            return Err(ContractError::TooManyPinnedPosts {
+                max: MAX_PINNED_POSTS,
+            });
+        }
+        pinned.push(seq);
+        PINNED_POSTS.save(deps.storage, &hub_addr, &pinned)?;
+
+        Ok(Response::new().add_attribute("method", "pin_post"))
+    }
+
+    pub fn unpin_post(
+        deps: DepsMut,
+        info: MessageInfo,
+        post_id: String,
+    ) -> Result<Response, ContractError> {
+        let ((hub_addr, seq), _) = load_own_post(deps.storage, &info.sender, &post_id)?;
+
+        let mut pinned = PINNED_POSTS
+            .may_load(deps.storage, &hub_addr)?
+            .unwrap_or_default();
+        if !pinned.contains(&seq) {
+            return Err(ContractError::PostNotPinned { id: post_id });
+        }
+        pinned.retain(|pinned_seq| *pinned_seq != seq);
+        PINNED_POSTS.save(deps.storage, &hub_addr, &pinned)?;
+
+        Ok(Response::new().add_attribute("method", "unpin_post"))
+    }
+
+    pub fn create_series(
+        deps: DepsMut,
+        info: MessageInfo,
+        title: String,
+        post_ids: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        let hub_addr = info.sender.as_str();
+        if !HUBS.has(deps.storage, hub_addr) {
+            return Err(ContractError::HubNotFound {});
+        }
+
+        let limits = CONFIG.load(deps.storage)?.limits;
+        check_text("title", &title, &limits.title, &limits, false)?;
+
+        let id = SERIES_SEQ
+            .may_load(deps.storage, hub_addr)?
+            .unwrap_or_default();
+        let mut series = Series {
+            id,
+            title,
+            post_ids: vec![],
+        };
+        for post_id in post_ids {
+            add_to_series(deps.storage, hub_addr, &mut series, post_id)?;
+        }
+        SERIES.save(deps.storage, (hub_addr, id), &series)?;
+        SERIES_SEQ.save(deps.storage, hub_addr, &(id + 1))?;
+
+        Ok(Response::new()
+            .add_attribute("method", "create_series")
+            .add_attribute("series_id", id.to_string())
+            .set_data(to_json_binary(&id)?))
+    }
+
+    pub fn append_to_series(
+        deps: DepsMut,
+        info: MessageInfo,
+        series_id: u64,
+        post_id: String,
+    ) -> Result<Response, ContractError> {
+        let hub_addr = info.sender.as_str();
+        let mut series = load_series(deps.storage, hub_addr, series_id)?;
+
+        add_to_series(deps.storage, hub_addr, &mut series, post_id)?;
+        SERIES.save(deps.storage, (hub_addr, series_id), &series)?;
+
+        Ok(Response::new().add_attribute("method", "append_to_series"))
+    }
+
+    pub fn reorder_series(
+        deps: DepsMut,
+        info: MessageInfo,
+        series_id: u64,
+        post_ids: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        let hub_addr = info.sender.as_str();
+        let mut series = load_series(deps.storage, hub_addr, series_id)?;
+
+        let mut current = series.post_ids.clone();
+        let mut reordered = post_ids.clone();
+        current.sort();
+        reordered.sort();
+        if current != reordered {
+            return Err(ContractError::InvalidSeriesOrder);
+        }
+        series.post_ids = post_ids;
+        SERIES.save(deps.storage, (hub_addr, series_id), &series)?;
+
+        Ok(Response::new().add_attribute("method", "reorder_series"))
+    }
+
+    pub fn remove_from_series(
+        deps: DepsMut,
+        info: MessageInfo,
+        series_id: u64,
+        post_id: String,
+    ) -> Result<Response, ContractError> {
+        let hub_addr = info.sender.as_str();
+        let mut series = load_series(deps.storage, hub_addr, series_id)?;
+
+        if !series.post_ids.contains(&post_id) {
+            return Err(ContractError::PostNotInSeries { id: post_id });
+        }
+        series.post_ids.retain(|id| *id != post_id);
+        SERIES.save(deps.storage, (hub_addr, series_id), &series)?;
+
+        Ok(Response::new().add_attribute("method", "remove_from_series"))
+    }
+
+    pub fn delete_series(
+        deps: DepsMut,
+        info: MessageInfo,
+        series_id: u64,
+    ) -> Result<Response, ContractError> {
+        let hub_addr = info.sender.as_str();
+        load_series(deps.storage, hub_addr, series_id)?;
+
+        SERIES.remove(deps.storage, (hub_addr, series_id));
+
+        Ok(Response::new().add_attribute("method", "delete_series"))
+    }
+
+    fn load_series(
+        storage: &dyn Storage,
+        hub_addr: &str,
+        series_id: u64,
+    ) -> Result<Series, ContractError> {
+        SERIES
+            .may_load(storage, (hub_addr, series_id))?
+            .ok_or(ContractError::SeriesNotFound { id: series_id })
+    }
+
+    /// Series only collect posts of their own hub, each at most once.
+    fn add_to_series(
+        storage: &dyn Storage,
+        hub_addr: &str,
+        series: &mut Series,
+        post_id: String,
+    ) -> Result<(), ContractError> {
+        if find_hub_post(storage, hub_addr, &post_id)?.is_none() {
+            return Err(ContractError::PostNotFound);
+        }
+        if series.post_ids.contains(&post_id) {
+            return Err(ContractError::PostAlreadyInSeries { id: post_id });
+        }
+        if series.post_ids.len() >= MAX_SERIES_LEN {
+            return Err(ContractError::SeriesFull {
+                max: MAX_SERIES_LEN,
+            });
+        }
+        series.post_ids.push(post_id);
+        Ok(())
+    }
+
+    pub fn set_post_published(
+        deps: DepsMut,
+        info: MessageInfo,
+        post_id: String,
+        published: bool,
+    ) -> Result<Response, ContractError> {
+        let ((hub_addr, seq), mut post) = load_own_post(deps.storage, &info.sender, &post_id)?;
+
+        if post.unpublished == published {
+            for tag in &post.tags {
+                HUB_TAGS.update(deps.storage, (&hub_addr, tag), |count| -> StdResult<_> {
+                    let count = count.unwrap_or_default();
+                    Ok(if published {
+                        count + 1
+                    } else {
+                        count.saturating_sub(1)
+                    })
+                })?;
+            }
+        }
+        post.unpublished = !published;
+        posts().save(deps.storage, (&hub_addr, seq), &post)?;
 
-    use crate::{
-        error::ContractError,
-        state::{Hub, Post, HUBS, HUB_ADDRESS, LIKES, SUBSCRIPTIONS, USER_LIKES},
-    };
+        let method = if published {
+            "republish_post"
+        } else {
+            "unpublish_post"
+        };
+        Ok(Response::new().add_attribute("method", method))
+    }
 
-    pub fn create_hub(
+    pub fn edit_post(
         deps: DepsMut,
-        _env: Env,
+        env: Env,
         info: MessageInfo,
-        hub_name: String,
-        need_pay: Coin,
+        post_id: String,
+        title: String,
+        summary: Option<String>,
+        content: String,
     ) -> Result<Response, ContractError> {
-        let sender_addr_str = info.sender.as_str().to_string();
+        let ((hub_addr, seq), mut post) = load_own_post(deps.storage, &info.sender, &post_id)?;
 
-        // Check if creator already has a hub
-        if HUBS.load(deps.storage, &sender_addr_str).is_ok() {
-            return Err(ContractError::CreatorAlreadyHasHub);
+        let limits = CONFIG.load(deps.storage)?.limits;
+        check_text("title", &title, &limits.title, &limits, false)?;
+        if let Some(summary) = &summary {
+            check_text("summary", summary, &limits.summary, &limits, true)?;
+        }
+        check_text("content", &content, &limits.content, &limits, true)?;
+        // The off-chain body is pinned by its digest, so an edit can't slip an inline one in
+        if post.content_ref.is_some() && !content.is_empty() {
+            return Err(ContractError::ContentRefWithContent);
         }
-        let new_hub = Hub {
-            creator: info.sender.clone(),
-            name: hub_name,
-            payment: need_pay,
-            subscribers: vec![info.sender.clone()],
-            posts: vec![],
-        };
-        HUBS.save(deps.storage, &sender_addr_str, &new_hub)?;
 
-        let mut hub_addresses = HUB_ADDRESS.load(deps.storage).unwrap_or_default();
-        hub_addresses.insert(0, sender_addr_str); // Insert the new address at the beginning of the vector
-        HUB_ADDRESS.save(deps.storage, &hub_addresses)?;
+        let revision = POST_REVISIONS
+            .prefix((&hub_addr, &post_id))
+            .keys(deps.storage, None, None, Order::Descending)
+            .next()
+            .transpose()?
+            .map_or(0, |last| last + 1);
+        POST_REVISIONS.save(
+            deps.storage,
+            (&hub_addr, &post_id, revision),
+            &PostRevision {
+                revision,
+                title: post.title,
+                summary: post.summary.clone(),
+                content: post.content,
+                content_ref: post.content_ref.clone(),
+                updated: post.updated,
+            },
+        )?;
 
-        Ok(Response::new().add_attribute("method", "create_hub"))
+        post.title = title;
+        if let Some(summary) = summary {
+            post.summary = summary;
+        }
+        post.content = content;
+        post.updated = env.block.time.seconds();
+        posts().save(deps.storage, (&hub_addr, seq), &post)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "edit_post")
+            .add_attribute("revision", revision.to_string()))
     }
 
-    pub fn subscribe_to_hub(
+    pub fn update_config(
+        deps: DepsMut,
+        info: MessageInfo,
+        admin: Option<Addr>,
+        limits: Option<Limits>,
+    ) -> Result<Response, ContractError> {
+        let mut config = CONFIG.load(deps.storage)?;
+        if info.sender != config.admin {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if let Some(admin) = admin {
+            config.admin = deps.api.addr_validate(admin.as_str())?;
+        }
+        if let Some(limits) = limits {
+            for (field, limit) in [
+                ("hub_name", &limits.hub_name),
+                ("title", &limits.title),
+                ("summary", &limits.summary),
+                ("content", &limits.content),
+                ("comment", &limits.comment),
+            ] {
+                if limit.min > limit.max {
+                    return Err(ContractError::InvalidLimit {
+                        field: field.to_string(),
+                    });
+                }
+            }
+            config.limits = limits;
+        }
+        CONFIG.save(deps.storage, &config)?;
+
+        Ok(Response::new().add_attribute("method", "update_config"))
+    }
+
+    pub fn create_sponsor_pool(
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         hub_addr: String,
+        beneficiaries: Option<Vec<Addr>>,
+        expires_at: u64,
     ) -> Result<Response, ContractError> {
-        // Check if the hub exists
         let hub = HUBS
             .load(deps.storage, &hub_addr)
             .map_err(|_| ContractError::HubNotFound)?;
 
-        let sent_funds = info
+        if expires_at <= env.block.time.seconds() {
+            return Err(ContractError::InvalidExpiry);
+        }
+
+        // Pools are funded in the hub's payment denom so claims can be paid from them directly
+        if let Some(coin) = info
+            .funds
+            .iter()
+            .find(|coin| coin.denom != hub.payment.denom)
+        {
+            return Err(ContractError::UnexpectedDenom {
+                denom: coin.denom.clone(),
+            });
+        }
+        let deposit = info
             .funds
             .iter()
             .find(|coin| coin.denom == hub.payment.denom)
             .map(|coin| coin.amount)
             .unwrap_or_default();
-
-        if !hub.payment.amount.is_zero() && sent_funds < hub.payment.amount {
+        if deposit.is_zero() {
             return Err(ContractError::InsufficientFunds);
         }
 
-        // Check if the user is already subscribed
-        let user_addr = info.sender;
-        if SUBSCRIPTIONS
-            .load(deps.storage, (&user_addr, &hub_addr))
-            .is_ok()
-        {
-            return Err(ContractError::AlreadySubscribed);
-        }
-
-        // Subscribe the user to the hub
-        SUBSCRIPTIONS.save(deps.storage, (&user_addr, &hub_addr), &true)?;
+        let beneficiaries = beneficiaries
+            .map(|addrs| {
+                addrs
+                    .iter()
+                    .map(|addr| deps.api.addr_validate(addr.as_str()))
+                    .collect::<StdResult<Vec<_>>>()
+            })
+            .transpose()?;
 
-        // Optionally, add the user to the hub's subscribers list
-        // This step depends on whether you want to maintain a list of subscribers in the Hub struct
-        let mut hub = hub;
-        hub.subscribers.push(user_addr.clone());
-        HUBS.save(deps.storage, &hub_addr, &hub)?;
+        let id = SPONSOR_POOL_COUNT
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            + 1;
+        let pool = SponsorPool {
+            id,
+            sponsor: info.sender,
+            hub_addr: hub.creator,
+            funds: Coin::new(deposit.u128(), hub.payment.denom),
+            beneficiaries,
+            expires_at,
+        };
+        SPONSOR_POOLS.save(deps.storage, id, &pool)?;
+        SPONSOR_POOL_COUNT.save(deps.storage, &id)?;
 
-        Ok(Response::new().add_attribute("method", "subscribe_to_hub"))
+        Ok(Response::new()
+            .add_attribute("method", "create_sponsor_pool")
+            .add_attribute("pool_id", id.to_string()))
     }
 
-    pub fn create_post(
+    pub fn claim_sponsored_subscription(
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
-        post_id: String,
-        title: String,
-        content: String,
+        pool_id: u64,
     ) -> Result<Response, ContractError> {
-        let hub_id = info.sender.as_str();
+        let mut pool = SPONSOR_POOLS
+            .load(deps.storage, pool_id)
+            .map_err(|_| ContractError::SponsorPoolNotFound { id: pool_id })?;
 
-        // Check if the hub exists before proceeding
-        if !HUBS.has(deps.storage, hub_id) {
-            return Err(ContractError::HubNotFound {});
+        if env.block.time.seconds() >= pool.expires_at {
+            return Err(ContractError::SponsorPoolExpired { id: pool_id });
         }
 
-        let mut hub: Hub = HUBS.load(deps.storage, hub_id)?;
+        if let Some(beneficiaries) = &pool.beneficiaries {
+            if !beneficiaries.contains(&info.sender) {
+                return Err(ContractError::NotPoolBeneficiary { id: pool_id });
+            }
+        }
 
-        let post = Post {
-            id: post_id.clone(),
-            title,
-            content,
-            updated: env.block.time.seconds(),
-        };
+        let hub_addr = pool.hub_addr.to_string();
+        let hub = HUBS
+            .load(deps.storage, &hub_addr)
+            .map_err(|_| ContractError::HubNotFound)?;
 
-        hub.posts.insert(0, post);
+        let price = hub.subscription_price()?;
+        if pool.funds.amount < price.amount {
+            return Err(ContractError::InsufficientFunds);
+        }
+        pool.funds.amount -= price.amount;
+        SPONSOR_POOLS.save(deps.storage, pool_id, &pool)?;
 
-        HUBS.save(deps.storage, hub_id, &hub)?;
-        LIKES.save(deps.storage, &post_id, &0u64)?;
-        SUBSCRIPTIONS.save(deps.storage, (&info.sender, hub_id), &true)?;
+        add_subscriber(deps.storage, &hub_addr, hub, info.sender)?;
 
-        Ok(Response::new().add_attribute("method", "create_post"))
+        Ok(Response::new().add_attribute("method", "claim_sponsored_subscription"))
     }
 
-    pub fn like_post(
+    pub fn reclaim_sponsor_pool(
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
-        post_id: String,
+        pool_id: u64,
     ) -> Result<Response, ContractError> {
-        let mut likes = LIKES.load(deps.storage, &post_id)?;
+        let pool = SPONSOR_POOLS
+            .load(deps.storage, pool_id)
+            .map_err(|_| ContractError::SponsorPoolNotFound { id: pool_id })?;
 
-        if USER_LIKES.has(deps.storage, (&info.sender, &post_id)) {
-            return Err(ContractError::PostAlreadyLiked { id: post_id });
+        if info.sender != pool.sponsor {
+            return Err(ContractError::Unauthorized);
         }
 
-        likes += 1;
-        LIKES.save(deps.storage, &post_id, &likes)?;
+        if env.block.time.seconds() < pool.expires_at {
+            return Err(ContractError::SponsorPoolNotExpired { id: pool_id });
+        }
 
-        USER_LIKES.save(deps.storage, (&info.sender, &post_id), &true)?;
+        SPONSOR_POOLS.remove(deps.storage, pool_id);
 
-        Ok(Response::new().add_attribute("method", "like_post"))
+        let mut resp = Response::new().add_attribute("method", "reclaim_sponsor_pool");
+        if !pool.funds.amount.is_zero() {
+            resp = resp.add_message(BankMsg::Send {
+                to_address: pool.sponsor.into_string(),
+                amount: vec![pool.funds],
+            });
+        }
+
+        Ok(resp)
     }
 }
 
 pub mod query {
-    use crate::state::{Hub, Post, HUBS, HUB_ADDRESS, LIKES, SUBSCRIPTIONS, USER_LIKES};
-    use cosmwasm_std::{to_json_binary, Addr, Binary, Deps, Order, StdResult};
+    use crate::{
+        msg::{
+            CommentListing, PollResultsResponse, PostCommentsResponse, PostListing, PostResponse,
+            SeriesResponse, Supporter, TagCount,
+        },
+        state::{
+            comment_replies, find_hub_post, posts, Hub, Post, PostRevision, RepostRef, Series,
+            Visibility, COMMENTS, COMMENT_COUNTS, CONFIG, ENCRYPTION_KEYS, EXPIRING_POSTS, HUBS,
+            HUB_ADDRESS, HUB_FEED, HUB_TAGS, LIKES, PINNED_POSTS, POLL_TALLIES, POLL_VOTES,
+            POST_KEYS, POST_REVISIONS, REPOST_COUNTS, SERIES, SPONSOR_POOLS, SUBSCRIPTIONS,
+            SUPPORTERS, TAGGED_POSTS, USER_LIKES,
+        },
+    };
+    use cosmwasm_std::{to_json_binary, Addr, Binary, Deps, Env, Order, StdError, StdResult};
+    use cw_storage_plus::Bound;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    const DEFAULT_LIMIT: u32 = 10;
+    const MAX_LIMIT: u32 = 30;
+
+    pub fn query_config(deps: Deps) -> StdResult<Binary> {
+        to_json_binary(&CONFIG.load(deps.storage)?)
+    }
 
     pub fn query_hub(deps: Deps, creator: Addr) -> StdResult<Binary> {
-        let hub = HUBS.load(deps.storage, &creator.as_str())?;
-        // TODO: should return newest or none Posts, not all
+        let hub = HUBS.load(deps.storage, creator.as_str())?;
         to_json_binary(&hub)
     }
 
@@ -190,53 +1662,553 @@ pub mod query {
 
     pub fn query_hub_posts(
         deps: Deps,
+        env: Env,
         user_addr: Addr,
         hub_addr: String,
         page: u64,
         size: u64,
+        lang: Option<String>,
     ) -> StdResult<Binary> {
         // Check if the user is subscribed to the hub
         let is_subscribed = SUBSCRIPTIONS
             .load(deps.storage, (&user_addr, &hub_addr))
             .unwrap_or(false);
 
-        let hub = match HUBS.may_load(deps.storage, &hub_addr) {
-            Ok(Some(hub)) => hub,
-            _ => return to_json_binary(&Vec::<Post>::new()), // Return empty Vec if hub not found or error occurs
-        };
+        if !HUBS.has(deps.storage, &hub_addr) {
+            return to_json_binary(&Vec::<PostResponse>::new()); // Return empty Vec if hub not found
+        }
+
+        // Newest first by publish time, so a scheduled post lands on top when it goes live;
+        // unpublished and scheduled ones only show to the creator
+        let is_creator = user_addr.as_str() == hub_addr;
+        let now = env.block.time.seconds();
+        let visible = |post: &Post| post.is_live(now) || (is_creator && !post.is_expired(now));
+        let pinned = PINNED_POSTS
+            .may_load(deps.storage, &hub_addr)?
+            .unwrap_or_default();
+        let hub_posts = HUB_FEED
+            .sub_prefix(&hub_addr)
+            .keys(deps.storage, None, None, Order::Descending)
+            .filter(|key| match key {
+                Ok((_, seq)) => !pinned.contains(seq),
+                Err(_) => true,
+            })
+            .map(|key| key.and_then(|(_, seq)| posts().load(deps.storage, (&hub_addr, seq))))
+            .filter(|item| match item {
+                Ok(post) => visible(post),
+                Err(_) => true,
+            });
+
+        // Everyone can browse titles and summaries; bodies are for subscribers unless public
+        let can_read = is_subscribed || is_creator;
+        let mut views = vec![];
 
-        let posts = if is_subscribed {
-            // If subscribed, paginate normally
-            let start = page.saturating_sub(1).saturating_mul(size) as usize;
-            let end = start.saturating_add(size as usize).min(hub.posts.len());
+        // Pinned posts lead the first page, on top of its regular `size` posts
+        if page <= 1 {
+            for seq in &pinned {
+                let post = posts().load(deps.storage, (&hub_addr, *seq))?;
+                if visible(&post) {
+                    let post = localized(post, lang.as_deref());
+                    let mut view = post_view(deps, now, &hub_addr, post, &user_addr, can_read)?;
+                    view.pinned = true;
+                    views.push(view);
+                }
+            }
+        }
+
+        let start = page.saturating_sub(1).saturating_mul(size) as usize;
+        for item in hub_posts.skip(start).take(size as usize) {
+            let post = localized(item?, lang.as_deref());
+            views.push(post_view(deps, now, &hub_addr, post, &user_addr, can_read)?);
+        }
+
+        to_json_binary(&views)
+    }
+
+    fn localized(mut post: Post, lang: Option<&str>) -> Post {
+        if let Some(lang) = lang {
+            post.select_variant(lang);
+        }
+        post
+    }
+
+    /// What `user_addr` gets to see of a post: everything if it is public or they can read the
+    /// hub's premium content, otherwise only the teaser.
+    fn post_view(
+        deps: Deps,
+        now: u64,
+        hub_addr: &str,
+        mut post: Post,
+        user_addr: &Addr,
+        can_read: bool,
+    ) -> StdResult<PostResponse> {
+        // The original is gated by its own hub, whoever reposted it
+        let original = match &post.repost_of {
+            Some(RepostRef {
+                hub_addr: source_hub,
+                post_id,
+            }) => match find_hub_post(deps.storage, source_hub, post_id)? {
+                Some((_, original)) if original.is_live(now) => {
+                    let can_read = user_addr.as_str() == source_hub
+                        || SUBSCRIPTIONS.has(deps.storage, (user_addr, source_hub));
+                    Some(Box::new(post_view(
+                        deps, now, source_hub, original, user_addr, can_read,
+                    )?))
+                }
+                _ => None,
+            },
+            None => None,
+        };
 
-            if start >= hub.posts.len() {
-                Vec::new()
-            } else {
-                hub.posts[start..end].to_vec()
+        if !can_read && post.visibility != Visibility::Public {
+            post.content = String::new();
+            post.content_ref = None;
+            post.attachments = vec![];
+            for variant in &mut post.variants {
+                variant.content = String::new();
             }
+            return Ok(PostResponse {
+                post,
+                locked: true,
+                key_envelope: None,
+                pinned: false,
+                original,
+            });
+        }
+
+        let key_envelope = if post.encrypted {
+            POST_KEYS.may_load(deps.storage, (hub_addr, &post.id, user_addr))?
         } else {
-            // If not subscribed, return only the latest post
-            hub.posts.iter().take(1).cloned().collect()
+            None
         };
+        Ok(PostResponse {
+            post,
+            locked: false,
+            key_envelope,
+            pinned: false,
+            original,
+        })
+    }
 
-        to_json_binary(&posts)
+    pub fn query_scheduled_posts(
+        deps: Deps,
+        env: Env,
+        user_addr: Addr,
+        hub_addr: String,
+    ) -> StdResult<Binary> {
+        // Queued posts are only listed for the hub creator
+        if user_addr.as_str() != hub_addr {
+            return to_json_binary(&Vec::<Post>::new());
+        }
+
+        // Everything in the feed with a publish time still ahead, soonest first
+        let now = env.block.time.seconds();
+        let start = Bound::exclusive((now, u64::MAX));
+        let scheduled: Vec<Post> = HUB_FEED
+            .sub_prefix(&hub_addr)
+            .keys(deps.storage, Some(start), None, Order::Ascending)
+            .map(|key| key.and_then(|(_, seq)| posts().load(deps.storage, (&hub_addr, seq))))
+            .collect::<StdResult<_>>()?;
+
+        to_json_binary(&scheduled)
     }
 
-    pub fn query_post_likes(deps: Deps, post_id: String) -> StdResult<Binary> {
-        let likes = LIKES.load(deps.storage, &post_id)?;
+    pub fn query_post_likes(deps: Deps, hub_addr: String, post_id: String) -> StdResult<Binary> {
+        let likes = LIKES.load(deps.storage, (&hub_addr, &post_id))?;
         to_json_binary(&likes)
     }
 
+    pub fn query_post_reposts(deps: Deps, hub_addr: String, post_id: String) -> StdResult<Binary> {
+        let reposts = REPOST_COUNTS
+            .may_load(deps.storage, (&hub_addr, &post_id))?
+            .unwrap_or_default();
+        to_json_binary(&reposts)
+    }
+
     pub fn query_user_has_hub(deps: Deps, creator: Addr) -> StdResult<Binary> {
-        let has_hub = HUBS.load(deps.storage, &creator.as_str()).is_ok();
+        let has_hub = HUBS.load(deps.storage, creator.as_str()).is_ok();
         to_json_binary(&has_hub)
     }
 
-    pub fn query_user_post_liked(deps: Deps, user: Addr, post_id: String) -> StdResult<Binary> {
+    pub fn query_user_post_liked(
+        deps: Deps,
+        user: Addr,
+        hub_addr: String,
+        post_id: String,
+    ) -> StdResult<Binary> {
         let liked = USER_LIKES
-            .load(deps.storage, (&user, &post_id))
+            .load(deps.storage, (&user, &hub_addr, &post_id))
             .unwrap_or(false);
         to_json_binary(&liked)
     }
+
+    pub fn query_sponsor_pool(deps: Deps, pool_id: u64) -> StdResult<Binary> {
+        let pool = SPONSOR_POOLS.load(deps.storage, pool_id)?;
+        to_json_binary(&pool)
+    }
+
+    pub fn query_top_supporters(
+        deps: Deps,
+        hub_addr: String,
+        start_after: Option<Supporter>,
+        limit: Option<u32>,
+    ) -> StdResult<Binary> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let end = start_after
+            .as_ref()
+            .map(|last| Bound::exclusive((last.amount.u128(), &last.addr)));
+
+        let supporters: Vec<Supporter> = SUPPORTERS
+            .sub_prefix(&hub_addr)
+            .keys(deps.storage, None, end, Order::Descending)
+            .take(limit)
+            .map(|key| {
+                key.map(|(amount, addr)| Supporter {
+                    addr,
+                    amount: amount.into(),
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        to_json_binary(&supporters)
+    }
+
+    pub fn query_quote_subscription(deps: Deps, hub_addr: String) -> StdResult<Binary> {
+        let hub = HUBS.load(deps.storage, &hub_addr)?;
+        to_json_binary(&hub.subscription_price()?)
+    }
+
+    pub fn query_post_revisions(
+        deps: Deps,
+        env: Env,
+        user_addr: Addr,
+        hub_addr: String,
+        post_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Binary> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        // Same rules as the feed: only the creator sees posts that aren't live, and old bodies
+        // are as premium as the current one
+        let is_creator = user_addr.as_str() == hub_addr;
+        let now = env.block.time.seconds();
+        let post = find_hub_post(deps.storage, &hub_addr, &post_id)?
+            .map(|(_, post)| post)
+            .filter(|post| post.is_live(now) || (is_creator && !post.is_expired(now)))
+            .ok_or_else(|| StdError::not_found("Post"))?;
+        let can_read = is_creator
+            || post.visibility == Visibility::Public
+            || SUBSCRIPTIONS.has(deps.storage, (&user_addr, &hub_addr));
+
+        let revisions: Vec<PostRevision> = POST_REVISIONS
+            .prefix((&hub_addr, &post_id))
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(_, mut revision)| {
+                    if !can_read {
+                        revision.content = String::new();
+                        revision.content_ref = None;
+                    }
+                    revision
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        to_json_binary(&revisions)
+    }
+
+    pub fn query_encryption_key(deps: Deps, user: Addr) -> StdResult<Binary> {
+        let public_key = ENCRYPTION_KEYS.may_load(deps.storage, &user)?;
+        to_json_binary(&public_key)
+    }
+
+    pub fn query_posts_by_tag(
+        deps: Deps,
+        env: Env,
+        tag: String,
+        start_after: Option<(String, u64)>,
+        limit: Option<u32>,
+    ) -> StdResult<Binary> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let tag = tag.trim().to_lowercase();
+        let end = start_after
+            .as_ref()
+            .map(|(hub_addr, seq)| Bound::exclusive((hub_addr.as_str(), *seq)));
+
+        // Posts come grouped by hub, newest first within each; only published posts are listed,
+        // and only as teasers since there is no reader to check
+        let now = env.block.time.seconds();
+        let mut tagged = vec![];
+        for item in TAGGED_POSTS
+            .sub_prefix(&tag)
+            .keys(deps.storage, None, end, Order::Descending)
+        {
+            let (hub_addr, seq) = item?;
+            let post = posts().load(deps.storage, (&hub_addr, seq))?;
+            if !post.is_live(now) {
+                continue;
+            }
+            let post = post_view(deps, now, &hub_addr, post, &Addr::unchecked(""), false)?;
+            tagged.push(PostListing {
+                hub_addr,
+                seq,
+                post,
+            });
+            if tagged.len() == limit {
+                break;
+            }
+        }
+
+        to_json_binary(&tagged)
+    }
+
+    pub fn query_posts_by_author(
+        deps: Deps,
+        env: Env,
+        author: Addr,
+        start_after: Option<(String, u64)>,
+        limit: Option<u32>,
+    ) -> StdResult<Binary> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let end = start_after
+            .as_ref()
+            .map(|(hub_addr, seq)| Bound::exclusive((hub_addr.as_str(), *seq)));
+
+        // Same ordering and teaser rules as `query_posts_by_tag`
+        let now = env.block.time.seconds();
+        let mut authored = vec![];
+        for item in posts().idx.author.prefix(author.into_string()).range(
+            deps.storage,
+            None,
+            end,
+            Order::Descending,
+        ) {
+            let ((hub_addr, seq), post) = item?;
+            if !post.is_live(now) {
+                continue;
+            }
+            let post = post_view(deps, now, &hub_addr, post, &Addr::unchecked(""), false)?;
+            authored.push(PostListing {
+                hub_addr,
+                seq,
+                post,
+            });
+            if authored.len() == limit {
+                break;
+            }
+        }
+
+        to_json_binary(&authored)
+    }
+
+    pub fn query_hub_tags(
+        deps: Deps,
+        env: Env,
+        hub_addr: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Binary> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.as_deref().map(Bound::exclusive);
+
+        // The stored counts include published posts that are still scheduled or already
+        // expired, since those change with time alone; both are indexed by time, so only they
+        // are looked up to discount them
+        let now = env.block.time.seconds();
+        let scheduled = HUB_FEED.sub_prefix(&hub_addr).keys(
+            deps.storage,
+            Some(Bound::exclusive((now, u64::MAX))),
+            None,
+            Order::Ascending,
+        );
+        let expired = EXPIRING_POSTS.sub_prefix(&hub_addr).keys(
+            deps.storage,
+            None,
+            Some(Bound::inclusive((now, u64::MAX))),
+            Order::Ascending,
+        );
+        let mut not_live: BTreeMap<String, u64> = BTreeMap::new();
+        let mut seen = BTreeSet::new();
+        for key in scheduled.chain(expired) {
+            let (_, seq) = key?;
+            if !seen.insert(seq) {
+                continue;
+            }
+            let post = posts().load(deps.storage, (&hub_addr, seq))?;
+            if !post.unpublished {
+                for tag in post.tags {
+                    *not_live.entry(tag).or_default() += 1;
+                }
+            }
+        }
+
+        let mut tags = vec![];
+        for item in HUB_TAGS
+            .prefix(&hub_addr)
+            .range(deps.storage, start, None, Order::Ascending)
+        {
+            let (tag, count) = item?;
+            let count = count.saturating_sub(not_live.get(&tag).copied().unwrap_or_default());
+            if count > 0 {
+                tags.push(TagCount { tag, count });
+            }
+            if tags.len() == limit {
+                break;
+            }
+        }
+
+        to_json_binary(&tags)
+    }
+
+    pub fn query_hub_series(
+        deps: Deps,
+        env: Env,
+        user_addr: Addr,
+        hub_addr: String,
+    ) -> StdResult<Binary> {
+        let mut series: Vec<Series> = SERIES
+            .prefix(&hub_addr)
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, series)| series))
+            .collect::<StdResult<_>>()?;
+
+        // Same rules as a single series: ids of posts that aren't out yet stay hidden
+        let is_creator = user_addr.as_str() == hub_addr;
+        let now = env.block.time.seconds();
+        for series in &mut series {
+            let mut visible = Vec::with_capacity(series.post_ids.len());
+            for post_id in series.post_ids.drain(..) {
+                if let Some((_, post)) = find_hub_post(deps.storage, &hub_addr, &post_id)? {
+                    if post.is_live(now) || (is_creator && !post.is_expired(now)) {
+                        visible.push(post_id);
+                    }
+                }
+            }
+            series.post_ids = visible;
+        }
+
+        to_json_binary(&series)
+    }
+
+    pub fn query_series(
+        deps: Deps,
+        env: Env,
+        user_addr: Addr,
+        hub_addr: String,
+        series_id: u64,
+    ) -> StdResult<Binary> {
+        let series = SERIES.load(deps.storage, (&hub_addr, series_id))?;
+
+        let is_creator = user_addr.as_str() == hub_addr;
+        let can_read = is_creator || SUBSCRIPTIONS.has(deps.storage, (&user_addr, &hub_addr));
+        let now = env.block.time.seconds();
+
+        let mut views = vec![];
+        for post_id in &series.post_ids {
+            let Some((_, post)) = find_hub_post(deps.storage, &hub_addr, post_id)? else {
+                continue;
+            };
+            if post.is_live(now) || (is_creator && !post.is_expired(now)) {
+                views.push(post_view(deps, now, &hub_addr, post, &user_addr, can_read)?);
+            }
+        }
+
+        to_json_binary(&SeriesResponse {
+            id: series.id,
+            title: series.title,
+            posts: views,
+        })
+    }
+
+    pub fn query_poll_results(
+        deps: Deps,
+        env: Env,
+        hub_addr: String,
+        post_id: String,
+    ) -> StdResult<Binary> {
+        // A scheduled or unpublished post must not give its question away
+        let now = env.block.time.seconds();
+        let poll = find_hub_post(deps.storage, &hub_addr, &post_id)?
+            .filter(|(_, post)| post.is_live(now))
+            .and_then(|(_, post)| post.poll)
+            .ok_or_else(|| StdError::not_found("Poll"))?;
+
+        let closed = poll.is_closed(now);
+        let tallies = if poll.hide_results && !closed {
+            None
+        } else {
+            Some(
+                (0..poll.options.len() as u32)
+                    .map(|option| {
+                        POLL_TALLIES
+                            .may_load(deps.storage, (&hub_addr, &post_id, option))
+                            .map(Option::unwrap_or_default)
+                    })
+                    .collect::<StdResult<_>>()?,
+            )
+        };
+
+        to_json_binary(&PollResultsResponse {
+            poll,
+            closed,
+            tallies,
+        })
+    }
+
+    pub fn query_user_poll_vote(
+        deps: Deps,
+        user: Addr,
+        hub_addr: String,
+        post_id: String,
+    ) -> StdResult<Binary> {
+        let vote = POLL_VOTES.may_load(deps.storage, (&hub_addr, &post_id, &user))?;
+        to_json_binary(&vote)
+    }
+
+    pub fn query_post_comments(
+        deps: Deps,
+        hub_addr: String,
+        post_id: String,
+        parent_id: Option<u64>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Binary> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        // One level at a time, so a deep or busy thread can't outgrow a query
+        let parent_id = parent_id.unwrap_or_default();
+        let comments = comment_replies(deps.storage, &hub_addr, &post_id, parent_id, start_after)
+            .take(limit)
+            .map(|id| id.and_then(|id| comment_listing(deps, &hub_addr, &post_id, id)))
+            .collect::<StdResult<_>>()?;
+
+        to_json_binary(&PostCommentsResponse {
+            count: COMMENT_COUNTS
+                .may_load(deps.storage, (&hub_addr, &post_id))?
+                .unwrap_or_default(),
+            comments,
+        })
+    }
+
+    fn comment_listing(
+        deps: Deps,
+        hub_addr: &str,
+        post_id: &str,
+        id: u64,
+    ) -> StdResult<CommentListing> {
+        let mut comment = COMMENTS.load(deps.storage, (hub_addr, post_id, id))?;
+        if comment.hidden {
+            comment.body = String::new();
+        }
+        let has_replies = comment_replies(deps.storage, hub_addr, post_id, id, None)
+            .next()
+            .is_some();
+        Ok(CommentListing {
+            comment,
+            has_replies,
+        })
+    }
 }
@@ -0,0 +1,151 @@
+//! Client-side encryption for premium posts.
+//!
+//! A post body is encrypted once under a random content key with ChaCha20-Poly1305 and stored
+//! base64 encoded in `Post.content`. The content key is then wrapped for every subscriber's
+//! registered X25519 public key: an ephemeral key agreement, HKDF-SHA256 and another
+//! ChaCha20-Poly1305 seal. Envelopes are laid out as `ephemeral public key || nonce || sealed key`.
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use cosmwasm_std::Binary;
+use hkdf::Hkdf;
+use rand_core::{CryptoRng, RngCore};
+use sha2::Sha256;
+use thiserror::Error;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use crate::Post;
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+const WRAP_INFO: &[u8] = b"xionhub post key wrap";
+
+pub type ContentKey = [u8; KEY_LEN];
+
+#[derive(Error, Debug, PartialEq)]
+pub enum CryptoError {
+    #[error("post {id} is not encrypted")]
+    NotEncrypted { id: String },
+
+    #[error("malformed ciphertext")]
+    Malformed,
+
+    #[error("decryption failed")]
+    Decryption,
+}
+
+/// Generates an X25519 key pair; the public half is what gets registered with the contract.
+pub fn generate_keypair(rng: &mut (impl RngCore + CryptoRng)) -> ([u8; 32], [u8; 32]) {
+    let secret = StaticSecret::random_from_rng(rng);
+    let public = PublicKey::from(&secret);
+    (secret.to_bytes(), public.to_bytes())
+}
+
+/// Encrypts a post body under a fresh content key, returning the key and the base64 text to
+/// send as the post's `content`.
+pub fn encrypt_post_body(
+    plaintext: &str,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> (ContentKey, String) {
+    let mut content_key = [0u8; KEY_LEN];
+    rng.fill_bytes(&mut content_key);
+    let sealed = seal(&content_key, plaintext.as_bytes(), rng);
+    (content_key, Binary::from(sealed).to_base64())
+}
+
+/// Decrypts the body of an encrypted post with its unwrapped content key.
+pub fn decrypt_post_body(content_key: &ContentKey, post: &Post) -> Result<String, CryptoError> {
+    if !post.encrypted {
+        return Err(CryptoError::NotEncrypted {
+            id: post.id.clone(),
+        });
+    }
+    let sealed = Binary::from_base64(&post.content).map_err(|_| CryptoError::Malformed)?;
+    let plaintext = open(content_key, &sealed)?;
+    String::from_utf8(plaintext).map_err(|_| CryptoError::Malformed)
+}
+
+/// Wraps a content key for the holder of `recipient_public`.
+pub fn wrap_content_key(
+    content_key: &ContentKey,
+    recipient_public: &[u8; 32],
+    rng: &mut (impl RngCore + CryptoRng),
+) -> Binary {
+    let ephemeral = StaticSecret::random_from_rng(&mut *rng);
+    let ephemeral_public = PublicKey::from(&ephemeral);
+    let recipient_public = PublicKey::from(*recipient_public);
+    let wrapping_key = derive_wrapping_key(
+        &ephemeral.diffie_hellman(&recipient_public).to_bytes(),
+        &ephemeral_public,
+        &recipient_public,
+    );
+
+    let mut envelope = ephemeral_public.to_bytes().to_vec();
+    envelope.extend(seal(&wrapping_key, content_key, rng));
+    Binary::from(envelope)
+}
+
+/// Recovers a content key from an envelope wrapped for `recipient_secret`.
+pub fn unwrap_content_key(
+    envelope: &[u8],
+    recipient_secret: &[u8; 32],
+) -> Result<ContentKey, CryptoError> {
+    if envelope.len() < KEY_LEN {
+        return Err(CryptoError::Malformed);
+    }
+    let (ephemeral_public, sealed) = envelope.split_at(KEY_LEN);
+    let ephemeral_public: [u8; 32] = ephemeral_public
+        .try_into()
+        .map_err(|_| CryptoError::Malformed)?;
+    let ephemeral_public = PublicKey::from(ephemeral_public);
+
+    let recipient = StaticSecret::from(*recipient_secret);
+    let wrapping_key = derive_wrapping_key(
+        &recipient.diffie_hellman(&ephemeral_public).to_bytes(),
+        &ephemeral_public,
+        &PublicKey::from(&recipient),
+    );
+
+    open(&wrapping_key, sealed)?
+        .try_into()
+        .map_err(|_| CryptoError::Malformed)
+}
+
+fn derive_wrapping_key(
+    shared_secret: &[u8; 32],
+    ephemeral_public: &PublicKey,
+    recipient_public: &PublicKey,
+) -> ContentKey {
+    let mut salt = ephemeral_public.to_bytes().to_vec();
+    salt.extend_from_slice(recipient_public.as_bytes());
+
+    let mut key = [0u8; KEY_LEN];
+    Hkdf::<Sha256>::new(Some(&salt), shared_secret)
+        .expand(WRAP_INFO, &mut key)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    key
+}
+
+fn seal(key: &ContentKey, plaintext: &[u8], rng: &mut (impl RngCore + CryptoRng)) -> Vec<u8> {
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill_bytes(&mut nonce);
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .expect("in-memory encryption does not fail");
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend(ciphertext);
+    sealed
+}
+
+fn open(key: &ContentKey, sealed: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    if sealed.len() < NONCE_LEN {
+        return Err(CryptoError::Malformed);
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| CryptoError::Decryption)
+}
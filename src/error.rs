@@ -24,9 +24,144 @@ pub enum ContractError {
     #[error("PostNotFound")]
     PostNotFound,
 
+    #[error("InvalidPostId")]
+    InvalidPostId { id: String },
+
     #[error("PostAlreadyExists")]
     PostAlreadyExists { id: String },
 
     #[error("PostAlreadyLiked")]
     PostAlreadyLiked { id: String },
+
+    #[error("PostNotLiked")]
+    PostNotLiked { id: String },
+
+    #[error("InvalidExpiry")]
+    InvalidExpiry,
+
+    #[error("UnexpectedDenom")]
+    UnexpectedDenom { denom: String },
+
+    #[error("SponsorPoolNotFound")]
+    SponsorPoolNotFound { id: u64 },
+
+    #[error("SponsorPoolExpired")]
+    SponsorPoolExpired { id: u64 },
+
+    #[error("SponsorPoolNotExpired")]
+    SponsorPoolNotExpired { id: u64 },
+
+    #[error("NotPoolBeneficiary")]
+    NotPoolBeneficiary { id: u64 },
+
+    #[error("InvalidPublishTime")]
+    InvalidPublishTime,
+
+    #[error("PostNotScheduled")]
+    PostNotScheduled { id: String },
+
+    #[error("InvalidUri")]
+    InvalidUri { uri: String },
+
+    #[error("InvalidSha256")]
+    InvalidSha256 { hash: String },
+
+    #[error("InvalidSize")]
+    InvalidSize { size: u64 },
+
+    #[error("ContentRefWithContent")]
+    ContentRefWithContent,
+
+    #[error("InvalidEncryptionKey")]
+    InvalidEncryptionKey,
+
+    #[error("PostNotEncrypted")]
+    PostNotEncrypted { id: String },
+
+    #[error("NotSubscribed")]
+    NotSubscribed { addr: String },
+
+    #[error("TooManyTags")]
+    TooManyTags { max: usize },
+
+    #[error("InvalidTagLength")]
+    InvalidTagLength { tag: String, min: usize, max: usize },
+
+    #[error("InvalidTagCharacter")]
+    InvalidTagCharacter { tag: String },
+
+    #[error("CommentNotFound")]
+    CommentNotFound { id: u64 },
+
+    #[error("TooManyAttachments")]
+    TooManyAttachments { max: usize },
+
+    #[error("UnsupportedMimeType")]
+    UnsupportedMimeType { mime_type: String },
+
+    #[error("InvalidLength")]
+    InvalidLength { field: String, min: u32, max: u32 },
+
+    #[error("ControlCharacter")]
+    ControlCharacter { field: String },
+
+    #[error("TooManyPosts")]
+    TooManyPosts { max: u64 },
+
+    #[error("InvalidLimit")]
+    InvalidLimit { field: String },
+
+    #[error("TooManyPinnedPosts")]
+    TooManyPinnedPosts { max: usize },
+
+    #[error("PostAlreadyPinned")]
+    PostAlreadyPinned { id: String },
+
+    #[error("PostNotPinned")]
+    PostNotPinned { id: String },
+
+    #[error("SeriesNotFound")]
+    SeriesNotFound { id: u64 },
+
+    #[error("SeriesFull")]
+    SeriesFull { max: usize },
+
+    #[error("PostAlreadyInSeries")]
+    PostAlreadyInSeries { id: String },
+
+    #[error("PostNotInSeries")]
+    PostNotInSeries { id: String },
+
+    #[error("InvalidSeriesOrder")]
+    InvalidSeriesOrder,
+
+    #[error("InvalidPoll")]
+    InvalidPoll,
+
+    #[error("PollNotFound")]
+    PollNotFound { id: String },
+
+    #[error("PollClosed")]
+    PollClosed { id: String },
+
+    #[error("InvalidPollOption")]
+    InvalidPollOption { option: u32 },
+
+    #[error("AlreadyVoted")]
+    AlreadyVoted { id: String },
+
+    #[error("NotAnAuthor")]
+    NotAnAuthor { addr: String },
+
+    #[error("InvalidLanguageTag")]
+    InvalidLanguageTag { tag: String },
+
+    #[error("DuplicateLanguage")]
+    DuplicateLanguage { lang: String },
+
+    #[error("MissingDefaultLanguage")]
+    MissingDefaultLanguage,
+
+    #[error("TooManyVariants")]
+    TooManyVariants { max: usize },
 }
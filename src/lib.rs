@@ -1,4 +1,7 @@
+pub mod content;
 mod contract;
+#[cfg(all(any(test, feature = "client"), not(target_arch = "wasm32")))]
+pub mod crypto;
 mod error;
 mod msg;
 mod state;
@@ -7,9 +10,20 @@ mod state;
 pub mod multitest;
 
 use contract::{
-    exec::{create_hub, create_post, like_post, subscribe_to_hub},
+    exec::{
+        add_author, append_to_series, cancel_scheduled_post, claim_sponsored_subscription, comment,
+        create_hub, create_post, create_series, create_sponsor_pool, delete_comment, delete_post,
+        delete_series, edit_post, hide_comment, like_post, pin_post, prune_expired_posts,
+        publish_post_keys, reclaim_sponsor_pool, register_encryption_key, remove_author,
+        remove_from_series, reorder_series, repost, reschedule_post, set_post_published,
+        subscribe_to_hub, unlike_post, unpin_post, update_config, vote_poll,
+    },
     query::{
-        query_hub, query_hub_addresses, query_hub_posts, query_post_likes, query_user_has_hub,
+        query_config, query_encryption_key, query_hub, query_hub_addresses, query_hub_posts,
+        query_hub_series, query_hub_tags, query_poll_results, query_post_comments,
+        query_post_likes, query_post_reposts, query_post_revisions, query_posts_by_author,
+        query_posts_by_tag, query_quote_subscription, query_scheduled_posts, query_series,
+        query_sponsor_pool, query_top_supporters, query_user_has_hub, query_user_poll_vote,
         query_user_post_liked, query_user_subscriptions,
     },
 };
@@ -17,7 +31,9 @@ use cosmwasm_std::{entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Respons
 use error::ContractError;
 use msg::{ExecuteMsg, QueryMsg};
 
-use crate::msg::InstantiateMsg;
+use crate::msg::{InstantiateMsg, MigrateMsg};
+
+pub use state::{Attachment, ContentRef, Poll, Post, PostVariant, Visibility};
 
 #[entry_point]
 pub fn instantiate(
@@ -29,6 +45,11 @@ pub fn instantiate(
     contract::instantiate(deps, info)
 }
 
+#[entry_point]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    contract::migrate(deps, env)
+}
+
 #[entry_point]
 pub fn execute(
     deps: DepsMut,
@@ -37,24 +58,136 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::CreateHub { hub_name, need_pay } => {
-            create_hub(deps, env, info, hub_name, need_pay)
-        }
+        ExecuteMsg::CreateHub {
+            hub_name,
+            need_pay,
+            pay_what_you_want,
+            price_curve,
+        } => create_hub(
+            deps,
+            env,
+            info,
+            hub_name,
+            need_pay,
+            pay_what_you_want.unwrap_or(false),
+            price_curve,
+        ),
         ExecuteMsg::SubscribeHub { hub_addr } => {
             subscribe_to_hub(deps, info, hub_addr.into_string())
         }
-        ExecuteMsg::CreatePost {
+        ExecuteMsg::AddAuthor { author } => add_author(deps, info, author),
+        ExecuteMsg::RemoveAuthor { author } => remove_author(deps, info, author),
+        ExecuteMsg::CreatePost(msg) => create_post(deps, env, info, *msg),
+        ExecuteMsg::LikePost { hub_addr, post_id } => {
+            like_post(deps, env, info, hub_addr.into_string(), post_id)
+        }
+        ExecuteMsg::UnlikePost { hub_addr, post_id } => {
+            unlike_post(deps, info, hub_addr.into_string(), post_id)
+        }
+        ExecuteMsg::Repost {
+            source_hub,
+            source_post_id,
+            commentary,
+        } => repost(
+            deps,
+            env,
+            info,
+            source_hub.into_string(),
+            source_post_id,
+            commentary,
+        ),
+        ExecuteMsg::VotePoll {
+            hub_addr,
+            post_id,
+            option,
+        } => vote_poll(deps, env, info, hub_addr.into_string(), post_id, option),
+        ExecuteMsg::PruneExpiredPosts { hub_addr, limit } => {
+            prune_expired_posts(deps, env, hub_addr.into_string(), limit)
+        }
+        ExecuteMsg::EditPost {
             post_id,
             title,
+            summary,
             content,
-        } => create_post(deps, env, info, post_id, title, content),
-        ExecuteMsg::LikePost { post_id } => like_post(deps, info, post_id),
+        } => edit_post(deps, env, info, post_id, title, summary, content),
+        ExecuteMsg::DeletePost { post_id } => delete_post(deps, info, post_id),
+        ExecuteMsg::PinPost { post_id } => pin_post(deps, info, post_id),
+        ExecuteMsg::UnpinPost { post_id } => unpin_post(deps, info, post_id),
+        ExecuteMsg::CreateSeries { title, post_ids } => create_series(deps, info, title, post_ids),
+        ExecuteMsg::AppendToSeries { series_id, post_id } => {
+            append_to_series(deps, info, series_id, post_id)
+        }
+        ExecuteMsg::ReorderSeries {
+            series_id,
+            post_ids,
+        } => reorder_series(deps, info, series_id, post_ids),
+        ExecuteMsg::RemoveFromSeries { series_id, post_id } => {
+            remove_from_series(deps, info, series_id, post_id)
+        }
+        ExecuteMsg::DeleteSeries { series_id } => delete_series(deps, info, series_id),
+        ExecuteMsg::UnpublishPost { post_id } => set_post_published(deps, info, post_id, false),
+        ExecuteMsg::RepublishPost { post_id } => set_post_published(deps, info, post_id, true),
+        ExecuteMsg::ReschedulePost {
+            post_id,
+            publish_at,
+        } => reschedule_post(deps, env, info, post_id, publish_at),
+        ExecuteMsg::CancelScheduledPost { post_id } => {
+            cancel_scheduled_post(deps, env, info, post_id)
+        }
+        ExecuteMsg::Comment {
+            hub_addr,
+            post_id,
+            parent_id,
+            body,
+        } => comment(
+            deps,
+            env,
+            info,
+            hub_addr.into_string(),
+            post_id,
+            parent_id,
+            body,
+        ),
+        ExecuteMsg::HideComment {
+            post_id,
+            comment_id,
+        } => hide_comment(deps, info, post_id, comment_id),
+        ExecuteMsg::DeleteComment {
+            post_id,
+            comment_id,
+        } => delete_comment(deps, info, post_id, comment_id),
+        ExecuteMsg::RegisterEncryptionKey { public_key } => {
+            register_encryption_key(deps, info, public_key)
+        }
+        ExecuteMsg::PublishPostKeys { post_id, keys } => {
+            publish_post_keys(deps, info, post_id, keys)
+        }
+        ExecuteMsg::UpdateConfig { admin, limits } => update_config(deps, info, admin, limits),
+        ExecuteMsg::CreateSponsorPool {
+            hub_addr,
+            beneficiaries,
+            expires_at,
+        } => create_sponsor_pool(
+            deps,
+            env,
+            info,
+            hub_addr.into_string(),
+            beneficiaries,
+            expires_at,
+        ),
+        ExecuteMsg::ClaimSponsoredSubscription { pool_id } => {
+            claim_sponsored_subscription(deps, env, info, pool_id)
+        }
+        ExecuteMsg::ReclaimSponsorPool { pool_id } => {
+            reclaim_sponsor_pool(deps, env, info, pool_id)
+        }
     }
 }
 
 #[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
+        QueryMsg::Config {} => query_config(deps),
         QueryMsg::Hub { creator } => query_hub(deps, creator),
         QueryMsg::UserSubscriptions { user, page, size } => {
             query_user_subscriptions(deps, user, page, size)
@@ -65,9 +198,102 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             hub_addr,
             page,
             size,
-        } => query_hub_posts(deps, user_addr, hub_addr.into_string(), page, size),
-        QueryMsg::PostLikes { post_id } => query_post_likes(deps, post_id),
+            lang,
+        } => query_hub_posts(
+            deps,
+            env,
+            user_addr,
+            hub_addr.into_string(),
+            page,
+            size,
+            lang,
+        ),
+        QueryMsg::EncryptionKey { user } => query_encryption_key(deps, user),
+        QueryMsg::HubSeries {
+            user_addr,
+            hub_addr,
+        } => query_hub_series(deps, env, user_addr, hub_addr.into_string()),
+        QueryMsg::Series {
+            user_addr,
+            hub_addr,
+            series_id,
+        } => query_series(deps, env, user_addr, hub_addr.into_string(), series_id),
+        QueryMsg::PollResults { hub_addr, post_id } => {
+            query_poll_results(deps, env, hub_addr.into_string(), post_id)
+        }
+        QueryMsg::UserPollVote {
+            user,
+            hub_addr,
+            post_id,
+        } => query_user_poll_vote(deps, user, hub_addr.into_string(), post_id),
+        QueryMsg::PostComments {
+            hub_addr,
+            post_id,
+            parent_id,
+            start_after,
+            limit,
+        } => query_post_comments(
+            deps,
+            hub_addr.into_string(),
+            post_id,
+            parent_id,
+            start_after,
+            limit,
+        ),
+        QueryMsg::PostsByTag {
+            tag,
+            start_after,
+            limit,
+        } => query_posts_by_tag(deps, env, tag, start_after, limit),
+        QueryMsg::PostsByAuthor {
+            author,
+            start_after,
+            limit,
+        } => query_posts_by_author(deps, env, author, start_after, limit),
+        QueryMsg::HubTags {
+            hub_addr,
+            start_after,
+            limit,
+        } => query_hub_tags(deps, env, hub_addr.into_string(), start_after, limit),
+        QueryMsg::ScheduledPosts {
+            user_addr,
+            hub_addr,
+        } => query_scheduled_posts(deps, env, user_addr, hub_addr.into_string()),
+        QueryMsg::PostLikes { hub_addr, post_id } => {
+            query_post_likes(deps, hub_addr.into_string(), post_id)
+        }
+        QueryMsg::PostReposts { hub_addr, post_id } => {
+            query_post_reposts(deps, hub_addr.into_string(), post_id)
+        }
         QueryMsg::UserHasHub { creator } => query_user_has_hub(deps, creator),
-        QueryMsg::UserPostLiked { user, post_id } => query_user_post_liked(deps, user, post_id),
+        QueryMsg::UserPostLiked {
+            user,
+            hub_addr,
+            post_id,
+        } => query_user_post_liked(deps, user, hub_addr.into_string(), post_id),
+        QueryMsg::SponsorPool { pool_id } => query_sponsor_pool(deps, pool_id),
+        QueryMsg::TopSupporters {
+            hub_addr,
+            start_after,
+            limit,
+        } => query_top_supporters(deps, hub_addr.into_string(), start_after, limit),
+        QueryMsg::QuoteSubscription { hub_addr } => {
+            query_quote_subscription(deps, hub_addr.into_string())
+        }
+        QueryMsg::PostRevisions {
+            user_addr,
+            hub_addr,
+            post_id,
+            start_after,
+            limit,
+        } => query_post_revisions(
+            deps,
+            env,
+            user_addr,
+            hub_addr.into_string(),
+            post_id,
+            start_after,
+            limit,
+        ),
     }
 }
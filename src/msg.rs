@@ -1,30 +1,197 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin};
+use cosmwasm_std::{Addr, Binary, Coin, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::{
+    Attachment, Comment, ContentRef, Limits, Poll, Post, PostVariant, PriceCurve, Visibility,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {}
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
 #[cw_serde]
 pub enum ExecuteMsg {
     CreateHub {
         hub_name: String,
         need_pay: Coin,
+        pay_what_you_want: Option<bool>,
+        price_curve: Option<PriceCurve>,
     },
     SubscribeHub {
         hub_addr: Addr,
     },
-    CreatePost {
+    /// Lets `author` publish posts into the sender's hub.
+    AddAuthor {
+        author: Addr,
+    },
+    RemoveAuthor {
+        author: Addr,
+    },
+    CreatePost(Box<CreatePostMsg>),
+    LikePost {
+        hub_addr: Addr,
+        post_id: String,
+    },
+    UnlikePost {
+        hub_addr: Addr,
+        post_id: String,
+    },
+    /// Reposts another hub's post into the sender's hub, optionally with public commentary.
+    Repost {
+        source_hub: Addr,
+        source_post_id: String,
+        commentary: Option<String>,
+    },
+    /// `option` is the index into the poll's options.
+    VotePoll {
+        hub_addr: Addr,
+        post_id: String,
+        option: u32,
+    },
+    /// Deletes up to `limit` of the hub's expired posts; anyone may call it.
+    PruneExpiredPosts {
+        hub_addr: Addr,
+        limit: Option<u32>,
+    },
+    EditPost {
         post_id: String,
         title: String,
+        /// New public teaser; left out keeps the current one.
+        summary: Option<String>,
         content: String,
     },
-    LikePost {
+    DeletePost {
+        post_id: String,
+    },
+    /// Pins a post to the top of the hub; at most three at a time.
+    PinPost {
+        post_id: String,
+    },
+    UnpinPost {
+        post_id: String,
+    },
+    CreateSeries {
+        title: String,
+        post_ids: Vec<String>,
+    },
+    AppendToSeries {
+        series_id: u64,
+        post_id: String,
+    },
+    /// `post_ids` must hold exactly the posts already in the series.
+    ReorderSeries {
+        series_id: u64,
+        post_ids: Vec<String>,
+    },
+    RemoveFromSeries {
+        series_id: u64,
         post_id: String,
     },
+    DeleteSeries {
+        series_id: u64,
+    },
+    UnpublishPost {
+        post_id: String,
+    },
+    RepublishPost {
+        post_id: String,
+    },
+    ReschedulePost {
+        post_id: String,
+        publish_at: u64,
+    },
+    CancelScheduledPost {
+        post_id: String,
+    },
+    Comment {
+        hub_addr: Addr,
+        post_id: String,
+        parent_id: Option<u64>,
+        body: String,
+    },
+    HideComment {
+        post_id: String,
+        comment_id: u64,
+    },
+    /// Deletes the comment together with all replies to it.
+    DeleteComment {
+        post_id: String,
+        comment_id: u64,
+    },
+    RegisterEncryptionKey {
+        public_key: Binary,
+    },
+    PublishPostKeys {
+        post_id: String,
+        keys: Vec<PostKey>,
+    },
+    /// Admin only; fields left out keep their current value.
+    UpdateConfig {
+        admin: Option<Addr>,
+        limits: Option<Limits>,
+    },
+    CreateSponsorPool {
+        hub_addr: Addr,
+        beneficiaries: Option<Vec<Addr>>,
+        expires_at: u64,
+    },
+    ClaimSponsoredSubscription {
+        pool_id: u64,
+    },
+    ReclaimSponsorPool {
+        pool_id: u64,
+    },
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct CreatePostMsg {
+    /// Left out to have the contract assign `<hub address>/<sequence>`.
+    pub post_id: Option<String>,
+    pub title: String,
+    /// Public teaser shown to non-subscribers.
+    #[serde(default)]
+    pub summary: String,
+    pub content: String,
+    /// Block time (seconds) to publish at; until then only the creator sees the post.
+    pub publish_at: Option<u64>,
+    /// Set instead of `content` for bodies stored off-chain.
+    pub content_ref: Option<ContentRef>,
+    /// Marks `content` as base64 ciphertext for subscribers to decrypt.
+    #[serde(default)]
+    pub encrypted: bool,
+    /// Up to five tags of lowercase letters, digits and `-`.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    #[serde(default)]
+    pub visibility: Visibility,
+    pub poll: Option<Poll>,
+    /// Hub to publish into, for authors the hub's creator invited; defaults to the sender's hub.
+    pub hub_addr: Option<Addr>,
+    /// Block time (seconds) after which the post disappears.
+    pub expires_at: Option<u64>,
+    /// Language of `title` and `content`; required when adding `variants`.
+    pub lang: Option<String>,
+    #[serde(default)]
+    pub variants: Vec<PostVariant>,
+}
+
+#[cw_serde]
+pub struct PostKey {
+    pub subscriber: Addr,
+    /// Content key wrapped for the subscriber's registered public key.
+    pub envelope: Binary,
 }
 
 #[cw_serde]
 pub enum QueryMsg {
+    Config {},
     Hub {
         creator: Addr,
     },
@@ -42,8 +209,15 @@ pub enum QueryMsg {
         hub_addr: Addr,
         page: u64,
         size: u64,
+        /// Preferred language; posts without that variant come in their default one.
+        lang: Option<String>,
     },
     PostLikes {
+        hub_addr: Addr,
+        post_id: String,
+    },
+    PostReposts {
+        hub_addr: Addr,
         post_id: String,
     },
     UserHasHub {
@@ -51,6 +225,144 @@ pub enum QueryMsg {
     },
     UserPostLiked {
         user: Addr,
+        hub_addr: Addr,
+        post_id: String,
+    },
+    SponsorPool {
+        pool_id: u64,
+    },
+    /// Ordered by amount, largest first; pass the last supporter of a page as `start_after`.
+    TopSupporters {
+        hub_addr: Addr,
+        start_after: Option<Supporter>,
+        limit: Option<u32>,
+    },
+    QuoteSubscription {
+        hub_addr: Addr,
+    },
+    EncryptionKey {
+        user: Addr,
+    },
+    HubSeries {
+        user_addr: Addr,
+        hub_addr: Addr,
+    },
+    Series {
+        user_addr: Addr,
+        hub_addr: Addr,
+        series_id: u64,
+    },
+    PollResults {
+        hub_addr: Addr,
+        post_id: String,
+    },
+    UserPollVote {
+        user: Addr,
+        hub_addr: Addr,
+        post_id: String,
+    },
+    PostComments {
+        hub_addr: Addr,
+        post_id: String,
+        /// Lists the replies to this comment instead of the top-level comments.
+        parent_id: Option<u64>,
+        /// Id of the last comment on the previous page.
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    PostsByTag {
+        tag: String,
+        /// (hub address, sequence) of the last post on the previous page.
+        start_after: Option<(String, u64)>,
+        limit: Option<u32>,
+    },
+    PostsByAuthor {
+        author: Addr,
+        /// (hub address, sequence) of the last post on the previous page.
+        start_after: Option<(String, u64)>,
+        limit: Option<u32>,
+    },
+    HubTags {
+        hub_addr: Addr,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    ScheduledPosts {
+        user_addr: Addr,
+        hub_addr: Addr,
+    },
+    /// Revision bodies are blanked for callers who can't read the post.
+    PostRevisions {
+        user_addr: Addr,
+        hub_addr: Addr,
         post_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
     },
 }
+
+#[cw_serde]
+pub struct Supporter {
+    pub addr: Addr,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PostResponse {
+    #[serde(flatten)]
+    pub post: Post,
+    /// Set when the body was withheld because the user can't read the hub's premium content.
+    pub locked: bool,
+    /// The content key wrapped for the querying user, on encrypted posts.
+    pub key_envelope: Option<Binary>,
+    /// The original of a repost, locked unless the user can read the original's hub. `None` once
+    /// the original is gone or hidden.
+    pub original: Option<Box<PostResponse>>,
+    /// Pinned by the hub creator; pinned posts lead the first page.
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PostListing {
+    pub hub_addr: String,
+    pub seq: u64,
+    pub post: PostResponse,
+}
+
+#[cw_serde]
+pub struct TagCount {
+    pub tag: String,
+    pub count: u64,
+}
+
+#[cw_serde]
+pub struct CommentListing {
+    pub comment: Comment,
+    /// Whether there are replies to fetch with the comment as `parent_id`.
+    pub has_replies: bool,
+}
+
+#[cw_serde]
+pub struct PostCommentsResponse {
+    /// Total number of comments on the post, replies included.
+    pub count: u64,
+    /// A page of comments under the requested parent.
+    pub comments: Vec<CommentListing>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SeriesResponse {
+    pub id: u64,
+    pub title: String,
+    /// The series' posts in order, gated like `HubPosts`.
+    pub posts: Vec<PostResponse>,
+}
+
+#[cw_serde]
+pub struct PollResultsResponse {
+    pub poll: Poll,
+    pub closed: bool,
+    /// Votes per option, `None` while the creator keeps results hidden until close.
+    pub tallies: Option<Vec<u64>>,
+}
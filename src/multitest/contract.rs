@@ -1,11 +1,15 @@
 use crate::{
     error::ContractError,
-    execute, instantiate,
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    execute, instantiate, migrate,
+    msg::{
+        CreatePostMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, PollResultsResponse,
+        PostCommentsResponse, PostKey, PostListing, PostResponse, QueryMsg, SeriesResponse,
+        Supporter, TagCount,
+    },
     query,
-    state::{Hub, Post},
+    state::{Config, Hub, Limits, Post, PostRevision, PriceCurve, Series, SponsorPool},
 };
-use cosmwasm_std::{Addr, Coin, StdResult};
+use cosmwasm_std::{from_json, Addr, Binary, Coin, StdResult, Storage};
 use cw_multi_test::{App, ContractWrapper, Executor};
 use uuid::Uuid;
 
@@ -17,7 +21,7 @@ impl XionHubContract {
     }
 
     pub fn store_code(app: &mut App) -> u64 {
-        let contract = ContractWrapper::new(execute, instantiate, query);
+        let contract = ContractWrapper::new(execute, instantiate, query).with_migrate(migrate);
         app.store_code(Box::new(contract))
     }
 
@@ -43,20 +47,699 @@ impl XionHubContract {
         .map_err(|err| err.downcast().unwrap())
     }
 
+    #[track_caller]
+    pub fn migrate(&self, app: &mut App, sender: &Addr, code_id: u64) -> StdResult<()> {
+        app.migrate_contract(sender.clone(), self.0.clone(), &MigrateMsg {}, code_id)
+            .map_err(|err| err.downcast().unwrap())
+            .map(|_| ())
+    }
+
+    /// Writes straight into the contract's storage, e.g. to seed a legacy layout before migrating.
+    pub fn set_raw_storage(&self, app: &mut App, key: &[u8], value: &[u8]) {
+        let namespace = format!("contract_data/{}", self.0);
+        let mut full_key = vec![];
+        for prefix in [b"wasm".as_slice(), namespace.as_bytes()] {
+            full_key.extend_from_slice(&(prefix.len() as u16).to_be_bytes());
+            full_key.extend_from_slice(prefix);
+        }
+        full_key.extend_from_slice(key);
+        app.storage_mut().set(&full_key, value);
+    }
+
     #[track_caller]
     pub fn create_hub(
         &self,
         app: &mut App,
         sender: &Addr,
-        hub_name: &str,
-        need_pay: Coin,
+        hub_name: &str,
+        need_pay: Coin,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::CreateHub {
+                hub_name: hub_name.to_string(),
+                need_pay,
+                pay_what_you_want: None,
+                price_curve: None,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn create_pay_what_you_want_hub(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        hub_name: &str,
+        min_pay: Coin,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::CreateHub {
+                hub_name: hub_name.to_string(),
+                need_pay: min_pay,
+                pay_what_you_want: Some(true),
+                price_curve: None,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn create_curve_priced_hub(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        hub_name: &str,
+        denom: &str,
+        price_curve: PriceCurve,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::CreateHub {
+                hub_name: hub_name.to_string(),
+                need_pay: Coin::new(0, denom),
+                pay_what_you_want: None,
+                price_curve: Some(price_curve),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn subscribe_to_hub(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        hub_addr: &Addr,
+        funds: &[Coin],
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::SubscribeHub {
+                hub_addr: hub_addr.clone(),
+            },
+            funds,
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn add_author(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        author: &Addr,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::AddAuthor {
+                author: author.clone(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn remove_author(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        author: &Addr,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::RemoveAuthor {
+                author: author.clone(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn create_post(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        post_id: Uuid,
+        title: &str,
+        content: &str,
+    ) -> Result<(), ContractError> {
+        self.create_post_with(
+            app,
+            sender,
+            CreatePostMsg {
+                post_id: Some(post_id.to_string()),
+                title: title.to_string(),
+                content: content.to_string(),
+                ..Default::default()
+            },
+        )
+        .map(|_| ())
+    }
+
+    /// Creates a post from a full message, returning the id the contract stored it under.
+    #[track_caller]
+    pub fn create_post_with(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        msg: CreatePostMsg,
+    ) -> Result<String, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::CreatePost(Box::new(msg)),
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| from_json(resp.data.unwrap()).unwrap())
+    }
+
+    #[track_caller]
+    pub fn create_scheduled_post(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        post_id: Uuid,
+        title: &str,
+        content: &str,
+        publish_at: u64,
+    ) -> Result<(), ContractError> {
+        self.create_post_with(
+            app,
+            sender,
+            CreatePostMsg {
+                post_id: Some(post_id.to_string()),
+                title: title.to_string(),
+                content: content.to_string(),
+                publish_at: Some(publish_at),
+                ..Default::default()
+            },
+        )
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn reschedule_post(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        post_id: &Uuid,
+        publish_at: u64,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::ReschedulePost {
+                post_id: post_id.to_string(),
+                publish_at,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn cancel_scheduled_post(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        post_id: &Uuid,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::CancelScheduledPost {
+                post_id: post_id.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn create_post_with_generated_id(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        title: &str,
+        content: &str,
+    ) -> Result<String, ContractError> {
+        self.create_post_with(
+            app,
+            sender,
+            CreatePostMsg {
+                title: title.to_string(),
+                content: content.to_string(),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn like_post(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        hub_addr: &Addr,
+        post_id: &(impl ToString + ?Sized),
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::LikePost {
+                hub_addr: hub_addr.clone(),
+                post_id: post_id.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn unlike_post(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        hub_addr: &Addr,
+        post_id: &(impl ToString + ?Sized),
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::UnlikePost {
+                hub_addr: hub_addr.clone(),
+                post_id: post_id.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    /// Reposts into the sender's hub, returning the id of the new post.
+    #[track_caller]
+    pub fn repost(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        source_hub: &Addr,
+        source_post_id: &str,
+        commentary: Option<&str>,
+    ) -> Result<String, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Repost {
+                source_hub: source_hub.clone(),
+                source_post_id: source_post_id.to_string(),
+                commentary: commentary.map(str::to_string),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| from_json(resp.data.unwrap()).unwrap())
+    }
+
+    #[track_caller]
+    pub fn vote_poll(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        hub_addr: &Addr,
+        post_id: &str,
+        option: u32,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::VotePoll {
+                hub_addr: hub_addr.clone(),
+                post_id: post_id.to_string(),
+                option,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn prune_expired_posts(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        hub_addr: &Addr,
+        limit: Option<u32>,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::PruneExpiredPosts {
+                hub_addr: hub_addr.clone(),
+                limit,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn edit_post(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        post_id: &Uuid,
+        title: &str,
+        content: &str,
+    ) -> Result<(), ContractError> {
+        self.edit_post_with_summary(app, sender, post_id, title, None, content)
+    }
+
+    /// Like `edit_post`, also replacing the summary when one is given.
+    #[track_caller]
+    pub fn edit_post_with_summary(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        post_id: &Uuid,
+        title: &str,
+        summary: Option<&str>,
+        content: &str,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::EditPost {
+                post_id: post_id.to_string(),
+                title: title.to_string(),
+                summary: summary.map(str::to_string),
+                content: content.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn delete_post(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        post_id: &(impl ToString + ?Sized),
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::DeletePost {
+                post_id: post_id.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn pin_post(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        post_id: &(impl ToString + ?Sized),
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::PinPost {
+                post_id: post_id.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn unpin_post(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        post_id: &(impl ToString + ?Sized),
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::UnpinPost {
+                post_id: post_id.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn create_series(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        title: &str,
+        post_ids: &[&str],
+    ) -> Result<u64, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::CreateSeries {
+                title: title.to_string(),
+                post_ids: post_ids.iter().map(|id| id.to_string()).collect(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| from_json(resp.data.unwrap()).unwrap())
+    }
+
+    #[track_caller]
+    pub fn append_to_series(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        series_id: u64,
+        post_id: &str,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::AppendToSeries {
+                series_id,
+                post_id: post_id.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn reorder_series(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        series_id: u64,
+        post_ids: &[&str],
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::ReorderSeries {
+                series_id,
+                post_ids: post_ids.iter().map(|id| id.to_string()).collect(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn remove_from_series(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        series_id: u64,
+        post_id: &str,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::RemoveFromSeries {
+                series_id,
+                post_id: post_id.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn delete_series(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        series_id: u64,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::DeleteSeries { series_id },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn unpublish_post(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        post_id: &(impl ToString + ?Sized),
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::UnpublishPost {
+                post_id: post_id.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn republish_post(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        post_id: &(impl ToString + ?Sized),
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::RepublishPost {
+                post_id: post_id.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn comment(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        hub_addr: &Addr,
+        post_id: &Uuid,
+        parent_id: Option<u64>,
+        body: &str,
+    ) -> Result<u64, ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::Comment {
+                hub_addr: hub_addr.clone(),
+                post_id: post_id.to_string(),
+                parent_id,
+                body: body.to_string(),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|resp| from_json(resp.data.unwrap()).unwrap())
+    }
+
+    #[track_caller]
+    pub fn hide_comment(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        post_id: &Uuid,
+        comment_id: u64,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::HideComment {
+                post_id: post_id.to_string(),
+                comment_id,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn delete_comment(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        post_id: &Uuid,
+        comment_id: u64,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::DeleteComment {
+                post_id: post_id.to_string(),
+                comment_id,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn register_encryption_key(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        public_key: &[u8],
     ) -> Result<(), ContractError> {
         app.execute_contract(
             sender.clone(),
             self.0.clone(),
-            &ExecuteMsg::CreateHub {
-                hub_name: hub_name.to_string(),
-                need_pay,
+            &ExecuteMsg::RegisterEncryptionKey {
+                public_key: Binary::from(public_key),
             },
             &[],
         )
@@ -65,18 +748,64 @@ impl XionHubContract {
     }
 
     #[track_caller]
-    pub fn subscribe_to_hub(
+    pub fn publish_post_keys(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        post_id: &str,
+        keys: Vec<PostKey>,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::PublishPostKeys {
+                post_id: post_id.to_string(),
+                keys,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn update_config(
+        &self,
+        app: &mut App,
+        sender: &Addr,
+        admin: Option<&Addr>,
+        limits: Option<Limits>,
+    ) -> Result<(), ContractError> {
+        app.execute_contract(
+            sender.clone(),
+            self.0.clone(),
+            &ExecuteMsg::UpdateConfig {
+                admin: admin.cloned(),
+                limits,
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast().unwrap())
+        .map(|_| ())
+    }
+
+    #[track_caller]
+    pub fn create_sponsor_pool(
         &self,
         app: &mut App,
         sender: &Addr,
         hub_addr: &Addr,
+        beneficiaries: Option<Vec<Addr>>,
+        expires_at: u64,
         funds: &[Coin],
     ) -> Result<(), ContractError> {
         app.execute_contract(
             sender.clone(),
             self.0.clone(),
-            &ExecuteMsg::SubscribeHub {
+            &ExecuteMsg::CreateSponsorPool {
                 hub_addr: hub_addr.clone(),
+                beneficiaries,
+                expires_at,
             },
             funds,
         )
@@ -85,22 +814,16 @@ impl XionHubContract {
     }
 
     #[track_caller]
-    pub fn create_post(
+    pub fn claim_sponsored_subscription(
         &self,
         app: &mut App,
         sender: &Addr,
-        post_id: Uuid,
-        title: &str,
-        content: &str,
+        pool_id: u64,
     ) -> Result<(), ContractError> {
         app.execute_contract(
             sender.clone(),
             self.0.clone(),
-            &ExecuteMsg::CreatePost {
-                post_id: post_id.to_string(),
-                title: title.to_string(),
-                content: content.to_string(),
-            },
+            &ExecuteMsg::ClaimSponsoredSubscription { pool_id },
             &[],
         )
         .map_err(|err| err.downcast().unwrap())
@@ -108,24 +831,28 @@ impl XionHubContract {
     }
 
     #[track_caller]
-    pub fn like_post(
+    pub fn reclaim_sponsor_pool(
         &self,
         app: &mut App,
         sender: &Addr,
-        post_id: &Uuid,
+        pool_id: u64,
     ) -> Result<(), ContractError> {
         app.execute_contract(
             sender.clone(),
             self.0.clone(),
-            &ExecuteMsg::LikePost {
-                post_id: post_id.to_string(),
-            },
+            &ExecuteMsg::ReclaimSponsorPool { pool_id },
             &[],
         )
         .map_err(|err| err.downcast().unwrap())
         .map(|_| ())
     }
 
+    #[track_caller]
+    pub fn query_config(&self, app: &App) -> StdResult<Config> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::Config {})
+    }
+
     #[track_caller]
     pub fn query_hub(&self, app: &App, creator: &Addr) -> StdResult<Hub> {
         app.wrap().query_wasm_smart(
@@ -170,24 +897,56 @@ impl XionHubContract {
         hub_addr: &Addr,
         page: u64,
         size: u64,
-    ) -> StdResult<Vec<Post>> {
-        let resp: Vec<Post> = app.wrap().query_wasm_smart(
+    ) -> StdResult<Vec<PostResponse>> {
+        self.query_hub_posts_in(app, user_addr, hub_addr, page, size, None)
+    }
+
+    /// Like `query_hub_posts`, preferring the `lang` variant of each post.
+    #[track_caller]
+    pub fn query_hub_posts_in(
+        &self,
+        app: &App,
+        user_addr: &Addr,
+        hub_addr: &Addr,
+        page: u64,
+        size: u64,
+        lang: Option<&str>,
+    ) -> StdResult<Vec<PostResponse>> {
+        let resp: Vec<PostResponse> = app.wrap().query_wasm_smart(
             self.0.clone(),
             &QueryMsg::HubPosts {
                 user_addr: user_addr.clone(),
                 hub_addr: hub_addr.clone(),
                 page,
                 size,
+                lang: lang.map(str::to_string),
             },
         )?;
         Ok(resp)
     }
 
     #[track_caller]
-    pub fn query_post_likes(&self, app: &App, post_id: &Uuid) -> StdResult<u64> {
+    pub fn query_post_likes(
+        &self,
+        app: &App,
+        hub_addr: &Addr,
+        post_id: &(impl ToString + ?Sized),
+    ) -> StdResult<u64> {
         app.wrap().query_wasm_smart(
             self.0.clone(),
             &QueryMsg::PostLikes {
+                hub_addr: hub_addr.clone(),
+                post_id: post_id.to_string(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_post_reposts(&self, app: &App, hub_addr: &Addr, post_id: &str) -> StdResult<u64> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::PostReposts {
+                hub_addr: hub_addr.clone(),
                 post_id: post_id.to_string(),
             },
         )
@@ -204,15 +963,263 @@ impl XionHubContract {
     }
 
     #[track_caller]
-    pub fn query_user_post_liked(&self, app: &App, user: &Addr, post_id: &Uuid) -> StdResult<bool> {
+    pub fn query_user_post_liked(
+        &self,
+        app: &App,
+        user: &Addr,
+        hub_addr: &Addr,
+        post_id: &(impl ToString + ?Sized),
+    ) -> StdResult<bool> {
         app.wrap().query_wasm_smart(
             self.0.clone(),
             &QueryMsg::UserPostLiked {
                 user: user.clone(),
+                hub_addr: hub_addr.clone(),
+                post_id: post_id.to_string(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_sponsor_pool(&self, app: &App, pool_id: u64) -> StdResult<SponsorPool> {
+        app.wrap()
+            .query_wasm_smart(self.0.clone(), &QueryMsg::SponsorPool { pool_id })
+    }
+
+    #[track_caller]
+    pub fn query_top_supporters(
+        &self,
+        app: &App,
+        hub_addr: &Addr,
+        start_after: Option<Supporter>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<Supporter>> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::TopSupporters {
+                hub_addr: hub_addr.clone(),
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_quote_subscription(&self, app: &App, hub_addr: &Addr) -> StdResult<Coin> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::QuoteSubscription {
+                hub_addr: hub_addr.clone(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_post_revisions(
+        &self,
+        app: &App,
+        user_addr: &Addr,
+        hub_addr: &Addr,
+        post_id: &Uuid,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<PostRevision>> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::PostRevisions {
+                user_addr: user_addr.clone(),
+                hub_addr: hub_addr.clone(),
+                post_id: post_id.to_string(),
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_scheduled_posts(
+        &self,
+        app: &App,
+        user_addr: &Addr,
+        hub_addr: &Addr,
+    ) -> StdResult<Vec<Post>> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::ScheduledPosts {
+                user_addr: user_addr.clone(),
+                hub_addr: hub_addr.clone(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_hub_series(
+        &self,
+        app: &App,
+        user_addr: &Addr,
+        hub_addr: &Addr,
+    ) -> StdResult<Vec<Series>> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::HubSeries {
+                user_addr: user_addr.clone(),
+                hub_addr: hub_addr.clone(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_series(
+        &self,
+        app: &App,
+        user_addr: &Addr,
+        hub_addr: &Addr,
+        series_id: u64,
+    ) -> StdResult<SeriesResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::Series {
+                user_addr: user_addr.clone(),
+                hub_addr: hub_addr.clone(),
+                series_id,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_poll_results(
+        &self,
+        app: &App,
+        hub_addr: &Addr,
+        post_id: &str,
+    ) -> StdResult<PollResultsResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::PollResults {
+                hub_addr: hub_addr.clone(),
+                post_id: post_id.to_string(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_user_poll_vote(
+        &self,
+        app: &App,
+        user: &Addr,
+        hub_addr: &Addr,
+        post_id: &str,
+    ) -> StdResult<Option<u32>> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::UserPollVote {
+                user: user.clone(),
+                hub_addr: hub_addr.clone(),
+                post_id: post_id.to_string(),
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_post_comments(
+        &self,
+        app: &App,
+        hub_addr: &Addr,
+        post_id: &Uuid,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<PostCommentsResponse> {
+        self.query_comment_replies(app, hub_addr, post_id, None, start_after, limit)
+    }
+
+    #[track_caller]
+    pub fn query_comment_replies(
+        &self,
+        app: &App,
+        hub_addr: &Addr,
+        post_id: &Uuid,
+        parent_id: Option<u64>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<PostCommentsResponse> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::PostComments {
+                hub_addr: hub_addr.clone(),
                 post_id: post_id.to_string(),
+                parent_id,
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_posts_by_tag(
+        &self,
+        app: &App,
+        tag: &str,
+        start_after: Option<(String, u64)>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<PostListing>> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::PostsByTag {
+                tag: tag.to_string(),
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_posts_by_author(
+        &self,
+        app: &App,
+        author: &Addr,
+        start_after: Option<(String, u64)>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<PostListing>> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::PostsByAuthor {
+                author: author.clone(),
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    #[track_caller]
+    pub fn query_hub_tags(&self, app: &App, hub_addr: &Addr) -> StdResult<Vec<TagCount>> {
+        self.query_hub_tags_page(app, hub_addr, None, None)
+    }
+
+    #[track_caller]
+    pub fn query_hub_tags_page(
+        &self,
+        app: &App,
+        hub_addr: &Addr,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<TagCount>> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::HubTags {
+                hub_addr: hub_addr.clone(),
+                start_after: start_after.map(str::to_string),
+                limit,
             },
         )
     }
+
+    #[track_caller]
+    pub fn query_encryption_key(&self, app: &App, user: &Addr) -> StdResult<Option<Binary>> {
+        app.wrap().query_wasm_smart(
+            self.0.clone(),
+            &QueryMsg::EncryptionKey { user: user.clone() },
+        )
+    }
 }
 
 impl From<XionHubContract> for Addr {
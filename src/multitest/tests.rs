@@ -1,8 +1,22 @@
 use cosmwasm_std::{coin, coins, Addr};
 use cw_multi_test::App;
+use cw_storage_plus::Map;
+use rand_core::OsRng;
 use uuid::Uuid;
 
-use crate::{error::ContractError, state::Hub};
+use crate::{
+    content::{sha256_hex, verify_post_content, ContentError},
+    crypto::{
+        decrypt_post_body, encrypt_post_body, generate_keypair, unwrap_content_key,
+        wrap_content_key,
+    },
+    error::ContractError,
+    msg::{CreatePostMsg, PostKey, PostResponse, Supporter, TagCount},
+    state::{
+        Attachment, ContentRef, Hub, LengthLimit, Limits, Poll, PostRevision, PostVariant,
+        PriceCurve, Visibility, HUBS, SUBSCRIPTIONS,
+    },
+};
 
 use super::contract::XionHubContract;
 
@@ -31,7 +45,8 @@ fn test_create_hub() {
             name: "Test Channel".to_string(),
             payment: coin(0, XION),
             subscribers: vec![creator.clone()],
-            posts: vec![]
+            pay_what_you_want: false,
+            price_curve: None,
         }
     );
 
@@ -78,7 +93,8 @@ fn test_user_subscriptions() {
             name: "XionHub Office Channel".to_string(),
             payment: coin(0, XION),
             subscribers: vec![creator.clone(), user],
-            posts: vec![]
+            pay_what_you_want: false,
+            price_curve: None,
         }
     );
 }
@@ -107,8 +123,8 @@ fn test_user_subscriptions_with_token() {
         .create_hub(&mut app, &creator, hub_name, coin(100000, XION))
         .unwrap();
 
-    assert_eq!(contract.query_user_has_hub(&app, &creator).unwrap(), true);
-    assert_eq!(contract.query_user_has_hub(&app, &user2).unwrap(), false);
+    assert!(contract.query_user_has_hub(&app, &creator).unwrap());
+    assert!(!contract.query_user_has_hub(&app, &user2).unwrap());
 
     // Test InsufficientFunds when subscription fee is not met
     let err = contract
@@ -150,8 +166,9 @@ fn test_create_and_query_post() {
         .query_hub_posts(&app, &user, &creator, 1, 10)
         .unwrap();
     assert_eq!(resp.len(), 1);
-    assert_eq!(resp[0].title, "Hello World");
-    assert_eq!(resp[0].content, "This is my first post");
+    assert_eq!(resp[0].post.title, "Hello World");
+    assert!(resp[0].locked);
+    assert_eq!(resp[0].post.content, "");
 
     contract
         .create_post(
@@ -222,16 +239,19 @@ fn test_create_and_query_post() {
         .query_hub_posts(&app, &user, &creator, 1, 1)
         .unwrap();
     assert_eq!(resp2.len(), 1);
-    assert_eq!(resp2[0].title, "A NEW ERA FOR BURNT");
+    assert_eq!(resp2[0].post.title, "A NEW ERA FOR BURNT");
 
-    // if user is not subscribe hub, show latest post
+    // if user is not subscribe hub, show every post without its content
     let resp3 = contract
         .query_hub_posts(&app, &user, &creator, 1, 10)
         .unwrap();
-    assert_eq!(resp3.len(), 1);
-    assert_eq!(resp3[0].title, "A NEW ERA FOR BURNT");
+    assert_eq!(resp3.len(), 2);
+    assert_eq!(resp3[0].post.title, "A NEW ERA FOR BURNT");
+    assert!(resp3
+        .iter()
+        .all(|resp| resp.locked && resp.post.content.is_empty()));
 
-    // if user is subscribe hub, show all posts
+    // if user is subscribe hub, show all posts with content
     contract
         .subscribe_to_hub(&mut app, &user, &creator, &[])
         .unwrap();
@@ -239,6 +259,8 @@ fn test_create_and_query_post() {
         .query_hub_posts(&app, &user, &creator, 1, 10)
         .unwrap();
     assert_eq!(resp4.len(), 2);
+    assert!(!resp4[1].locked);
+    assert_eq!(resp4[1].post.content, "This is my first post");
 }
 
 #[test]
@@ -268,12 +290,16 @@ fn test_like_post() {
         )
         .unwrap();
 
-    contract.like_post(&mut app, &user, &post_id).unwrap();
+    contract
+        .like_post(&mut app, &user, &creator, &post_id)
+        .unwrap();
 
-    let resp = contract.query_post_likes(&app, &post_id).unwrap();
+    let resp = contract.query_post_likes(&app, &creator, &post_id).unwrap();
     assert_eq!(resp, 1);
 
-    let like_post_error = contract.like_post(&mut app, &user, &post_id).unwrap_err();
+    let like_post_error = contract
+        .like_post(&mut app, &user, &creator, &post_id)
+        .unwrap_err();
     assert_eq!(
         like_post_error,
         ContractError::PostAlreadyLiked {
@@ -281,12 +307,23 @@ fn test_like_post() {
         }
     );
 
-    assert_eq!(
-        contract
-            .query_user_post_liked(&app, &user, &post_id)
-            .unwrap(),
-        true
-    );
+    assert!(contract
+        .query_user_post_liked(&app, &user, &creator, &post_id)
+        .unwrap());
+
+    // the post has to exist in the hub it is liked through
+    let other_hub = Addr::unchecked("other");
+    contract
+        .create_hub(&mut app, &other_hub, "Other Channel", coin(0, XION))
+        .unwrap();
+    let err = contract
+        .like_post(&mut app, &user, &other_hub, &post_id)
+        .unwrap_err();
+    assert_eq!(err, ContractError::PostNotFound);
+    let err = contract
+        .like_post(&mut app, &user, &creator, &Uuid::new_v4())
+        .unwrap_err();
+    assert_eq!(err, ContractError::PostNotFound);
 }
 
 #[test]
@@ -333,3 +370,2712 @@ fn test_query_user_zero_subscriptions() {
         .unwrap();
     assert_eq!(resp.len(), 0);
 }
+
+#[test]
+fn test_sponsored_subscription() {
+    let mut app = App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &Addr::unchecked("sponsor"),
+                vec![coin(250, XION), coin(50, "uother")],
+            )
+            .unwrap()
+    });
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let sponsor = Addr::unchecked("sponsor");
+    let user = Addr::unchecked("user");
+    let user2 = Addr::unchecked("user2");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "VIP Channel", coin(100, XION))
+        .unwrap();
+
+    let expires_at = app.block_info().time.seconds() + 3600;
+    let err = contract
+        .create_sponsor_pool(
+            &mut app,
+            &sponsor,
+            &creator,
+            None,
+            expires_at,
+            &[coin(250, XION), coin(50, "uother")],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::UnexpectedDenom {
+            denom: "uother".to_string()
+        }
+    );
+
+    let err = contract
+        .create_sponsor_pool(
+            &mut app,
+            &sponsor,
+            &creator,
+            Some(vec![Addr::unchecked("Not A Valid Address")]),
+            expires_at,
+            &coins(250, XION),
+        )
+        .unwrap_err();
+    assert!(matches!(err, ContractError::Std(_)));
+
+    contract
+        .create_sponsor_pool(
+            &mut app,
+            &sponsor,
+            &creator,
+            Some(vec![user.clone()]),
+            expires_at,
+            &coins(250, XION),
+        )
+        .unwrap();
+
+    // only allowlisted users can claim
+    let err = contract
+        .claim_sponsored_subscription(&mut app, &user2, 1)
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotPoolBeneficiary { id: 1 });
+
+    contract
+        .claim_sponsored_subscription(&mut app, &user, 1)
+        .unwrap();
+    assert_eq!(
+        contract
+            .query_user_subscriptions(&app, &user, 1, 10)
+            .unwrap(),
+        vec!["VIP Channel".to_string()]
+    );
+    assert_eq!(
+        contract.query_sponsor_pool(&app, 1).unwrap().funds,
+        coin(150, XION)
+    );
+
+    let err = contract
+        .claim_sponsored_subscription(&mut app, &user, 1)
+        .unwrap_err();
+    assert_eq!(err, ContractError::AlreadySubscribed);
+
+    let err = contract
+        .reclaim_sponsor_pool(&mut app, &sponsor, 1)
+        .unwrap_err();
+    assert_eq!(err, ContractError::SponsorPoolNotExpired { id: 1 });
+
+    app.update_block(|block| block.time = block.time.plus_seconds(3600));
+
+    let err = contract
+        .reclaim_sponsor_pool(&mut app, &user, 1)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized);
+
+    contract
+        .reclaim_sponsor_pool(&mut app, &sponsor, 1)
+        .unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&sponsor, XION).unwrap(),
+        coin(150, XION)
+    );
+    assert!(contract.query_sponsor_pool(&app, 1).is_err());
+}
+
+#[test]
+fn test_pay_what_you_want_top_supporters() {
+    let mut app = App::new(|router, _, storage| {
+        for user in ["user", "user2", "user3"] {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked(user), coins(1000, XION))
+                .unwrap()
+        }
+    });
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let user = Addr::unchecked("user");
+    let user2 = Addr::unchecked("user2");
+    let user3 = Addr::unchecked("user3");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_pay_what_you_want_hub(&mut app, &creator, "Tip Jar", coin(100, XION))
+        .unwrap();
+
+    // the price is still a minimum
+    let err = contract
+        .subscribe_to_hub(&mut app, &user, &creator, &coins(50, XION))
+        .unwrap_err();
+    assert_eq!(err, ContractError::InsufficientFunds);
+
+    contract
+        .subscribe_to_hub(&mut app, &user, &creator, &coins(150, XION))
+        .unwrap();
+    contract
+        .subscribe_to_hub(&mut app, &user2, &creator, &coins(300, XION))
+        .unwrap();
+    // paying exactly the minimum is not a contribution
+    contract
+        .subscribe_to_hub(&mut app, &user3, &creator, &coins(100, XION))
+        .unwrap();
+
+    // contributions go straight to the creator; the minimums stay with the contract
+    assert_eq!(
+        app.wrap().query_balance(&creator, XION).unwrap(),
+        coin(250, XION)
+    );
+
+    let resp = contract
+        .query_top_supporters(&app, &creator, None, None)
+        .unwrap();
+    assert_eq!(
+        resp,
+        vec![
+            Supporter {
+                addr: user2.clone(),
+                amount: 200u128.into()
+            },
+            Supporter {
+                addr: user.clone(),
+                amount: 50u128.into()
+            },
+        ]
+    );
+
+    let first_page = contract
+        .query_top_supporters(&app, &creator, None, Some(1))
+        .unwrap();
+    assert_eq!(first_page.len(), 1);
+    assert_eq!(first_page[0].addr, user2);
+    let resp = contract
+        .query_top_supporters(&app, &creator, first_page.into_iter().last(), Some(1))
+        .unwrap();
+    assert_eq!(resp.len(), 1);
+    assert_eq!(resp[0].addr, user);
+}
+
+#[test]
+fn test_curve_priced_subscriptions() {
+    let mut app = App::new(|router, _, storage| {
+        for user in ["user", "user2", "user3"] {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked(user), coins(10000, XION))
+                .unwrap()
+        }
+    });
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let creator2 = Addr::unchecked("creator2");
+    let user = Addr::unchecked("user");
+    let user2 = Addr::unchecked("user2");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_curve_priced_hub(
+            &mut app,
+            &creator,
+            "Early Birds",
+            XION,
+            PriceCurve::Linear {
+                base: 100u128.into(),
+                slope: 50u128.into(),
+            },
+        )
+        .unwrap();
+
+    let quote = contract.query_quote_subscription(&app, &creator).unwrap();
+    assert_eq!(quote, coin(100, XION));
+    contract
+        .subscribe_to_hub(&mut app, &user, &creator, &[quote])
+        .unwrap();
+
+    // the second subscriber pays more
+    let quote = contract.query_quote_subscription(&app, &creator).unwrap();
+    assert_eq!(quote, coin(150, XION));
+    let err = contract
+        .subscribe_to_hub(&mut app, &user2, &creator, &coins(100, XION))
+        .unwrap_err();
+    assert_eq!(err, ContractError::InsufficientFunds);
+    contract
+        .subscribe_to_hub(&mut app, &user2, &creator, &[quote])
+        .unwrap();
+
+    contract
+        .create_curve_priced_hub(
+            &mut app,
+            &creator2,
+            "Compounding",
+            XION,
+            PriceCurve::Exponential {
+                base: 1000u128.into(),
+                growth_bps: 1000,
+            },
+        )
+        .unwrap();
+    contract
+        .subscribe_to_hub(&mut app, &user, &creator2, &coins(1000, XION))
+        .unwrap();
+    contract
+        .subscribe_to_hub(&mut app, &user2, &creator2, &coins(1100, XION))
+        .unwrap();
+    assert_eq!(
+        contract.query_quote_subscription(&app, &creator2).unwrap(),
+        coin(1210, XION)
+    );
+}
+
+#[test]
+fn test_migrate_posts_out_of_hub() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", &owner)
+            .unwrap();
+
+    // hubs used to carry their posts inline, newest first
+    let legacy_hub = r#"{
+        "creator": "creator",
+        "name": "Legacy Channel",
+        "payment": { "denom": "uxion", "amount": "0" },
+        "subscribers": ["creator"],
+        "posts": [
+            { "id": "second", "title": "Second", "content": "newer", "updated": 2 },
+            { "id": "first", "title": "First", "content": "older", "updated": 1 }
+        ]
+    }"#;
+    contract.set_raw_storage(&mut app, &HUBS.key("creator"), legacy_hub.as_bytes());
+    contract.set_raw_storage(&mut app, &SUBSCRIPTIONS.key((&creator, "creator")), b"true");
+
+    contract.migrate(&mut app, &owner, code_id).unwrap();
+
+    let resp = contract.query_hub(&app, &creator).unwrap();
+    assert_eq!(resp.name, "Legacy Channel");
+
+    let resp = contract
+        .query_hub_posts(&app, &creator, &creator, 1, 10)
+        .unwrap();
+    let titles: Vec<_> = resp.iter().map(|post| post.post.title.as_str()).collect();
+    assert_eq!(titles, vec!["Second", "First"]);
+    assert!(resp
+        .iter()
+        .all(|post| post.post.author.as_ref() == Some(&creator)));
+
+    // new posts keep going on top of the migrated ones
+    contract
+        .create_post(&mut app, &creator, Uuid::new_v4(), "Third", "newest")
+        .unwrap();
+    let resp = contract
+        .query_hub_posts(&app, &creator, &creator, 1, 2)
+        .unwrap();
+    let titles: Vec<_> = resp.iter().map(|post| post.post.title.as_str()).collect();
+    assert_eq!(titles, vec!["Third", "Second"]);
+}
+
+#[test]
+fn test_edit_post_revisions() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let user = Addr::unchecked("user");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "XionHub Office Channel", coin(0, XION))
+        .unwrap();
+
+    let post_id = Uuid::new_v4();
+    contract
+        .create_post(&mut app, &creator, post_id, "BTC outlook", "Bullish")
+        .unwrap();
+    let created = app.block_info().time.seconds();
+
+    let err = contract
+        .edit_post(&mut app, &user, &post_id, "BTC outlook", "Bearish")
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized);
+
+    let err = contract
+        .edit_post(
+            &mut app,
+            &creator,
+            &Uuid::new_v4(),
+            "BTC outlook",
+            "Bearish",
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::PostNotFound);
+
+    app.update_block(|block| block.time = block.time.plus_seconds(60));
+    contract
+        .edit_post(&mut app, &creator, &post_id, "BTC outlook", "Neutral")
+        .unwrap();
+    app.update_block(|block| block.time = block.time.plus_seconds(60));
+    contract
+        .edit_post(
+            &mut app,
+            &creator,
+            &post_id,
+            "BTC outlook (updated)",
+            "Bearish",
+        )
+        .unwrap();
+
+    let resp = contract
+        .query_hub_posts(&app, &creator, &creator, 1, 10)
+        .unwrap();
+    assert_eq!(resp.len(), 1);
+    assert_eq!(resp[0].post.title, "BTC outlook (updated)");
+    assert_eq!(resp[0].post.content, "Bearish");
+    assert_eq!(resp[0].post.updated, created + 120);
+
+    let resp = contract
+        .query_post_revisions(&app, &creator, &creator, &post_id, None, None)
+        .unwrap();
+    assert_eq!(
+        resp,
+        vec![
+            PostRevision {
+                revision: 0,
+                title: "BTC outlook".to_string(),
+                summary: String::new(),
+                content: "Bullish".to_string(),
+                content_ref: None,
+                updated: created,
+            },
+            PostRevision {
+                revision: 1,
+                title: "BTC outlook".to_string(),
+                summary: String::new(),
+                content: "Neutral".to_string(),
+                content_ref: None,
+                updated: created + 60,
+            },
+        ]
+    );
+
+    let resp = contract
+        .query_post_revisions(&app, &creator, &creator, &post_id, Some(0), Some(10))
+        .unwrap();
+    assert_eq!(resp.len(), 1);
+    assert_eq!(resp[0].revision, 1);
+
+    // non-subscribers only get the old titles
+    let resp = contract
+        .query_post_revisions(&app, &user, &creator, &post_id, None, None)
+        .unwrap();
+    assert_eq!(resp.len(), 2);
+    assert_eq!(resp[1].title, "BTC outlook");
+    assert!(resp.iter().all(|revision| revision.content.is_empty()));
+
+    contract
+        .subscribe_to_hub(&mut app, &user, &creator, &[])
+        .unwrap();
+    let resp = contract
+        .query_post_revisions(&app, &user, &creator, &post_id, None, None)
+        .unwrap();
+    assert_eq!(resp[0].content, "Bullish");
+
+    // nor can anyone but the creator read the history of an unpublished post
+    contract
+        .unpublish_post(&mut app, &creator, &post_id)
+        .unwrap();
+    assert!(contract
+        .query_post_revisions(&app, &user, &creator, &post_id, None, None)
+        .is_err());
+}
+
+#[test]
+fn test_delete_and_unpublish_post() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let user = Addr::unchecked("user");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "XionHub Office Channel", coin(0, XION))
+        .unwrap();
+    contract
+        .subscribe_to_hub(&mut app, &user, &creator, &[])
+        .unwrap();
+
+    let leaked = Uuid::new_v4();
+    let draft = Uuid::new_v4();
+    contract
+        .create_post(&mut app, &creator, leaked, "Leaked", "Oops")
+        .unwrap();
+    contract
+        .create_post(&mut app, &creator, draft, "Draft", "Not ready")
+        .unwrap();
+    contract
+        .like_post(&mut app, &user, &creator, &leaked)
+        .unwrap();
+    contract
+        .like_post(&mut app, &user, &creator, &draft)
+        .unwrap();
+
+    let err = contract.delete_post(&mut app, &user, &leaked).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized);
+    let err = contract
+        .delete_post(&mut app, &creator, &Uuid::new_v4())
+        .unwrap_err();
+    assert_eq!(err, ContractError::PostNotFound);
+    let err = contract
+        .unpublish_post(&mut app, &creator, &Uuid::new_v4())
+        .unwrap_err();
+    assert_eq!(err, ContractError::PostNotFound);
+
+    contract.delete_post(&mut app, &creator, &leaked).unwrap();
+    assert!(contract.query_post_likes(&app, &creator, &leaked).is_err());
+    assert!(!contract
+        .query_user_post_liked(&app, &user, &creator, &leaked)
+        .unwrap());
+
+    // unpublished posts stay visible to the creator only, and keep their likes
+    contract.unpublish_post(&mut app, &creator, &draft).unwrap();
+    assert!(contract
+        .query_hub_posts(&app, &user, &creator, 1, 10)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        contract
+            .query_hub_posts(&app, &creator, &creator, 1, 10)
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(
+        contract.query_post_likes(&app, &creator, &draft).unwrap(),
+        1
+    );
+
+    contract.republish_post(&mut app, &creator, &draft).unwrap();
+    let resp = contract
+        .query_hub_posts(&app, &user, &creator, 1, 10)
+        .unwrap();
+    assert_eq!(resp.len(), 1);
+    assert_eq!(resp[0].post.title, "Draft");
+}
+
+#[test]
+fn test_unique_and_generated_post_ids() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let creator2 = Addr::unchecked("creator2");
+    let user = Addr::unchecked("user");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "Channel", coin(0, XION))
+        .unwrap();
+    contract
+        .create_hub(&mut app, &creator2, "Other Channel", coin(0, XION))
+        .unwrap();
+
+    let post_id = Uuid::new_v4();
+    contract
+        .create_post(&mut app, &creator, post_id, "Hello World", "First")
+        .unwrap();
+    contract
+        .like_post(&mut app, &user, &creator, &post_id)
+        .unwrap();
+
+    // another hub can't reuse the id and reset its likes
+    let err = contract
+        .create_post(&mut app, &creator2, post_id, "Copycat", "Reset")
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PostAlreadyExists {
+            id: post_id.to_string()
+        }
+    );
+    assert_eq!(
+        contract.query_post_likes(&app, &creator, &post_id).unwrap(),
+        1
+    );
+
+    let generated = contract
+        .create_post_with_generated_id(&mut app, &creator, "Second", "Generated")
+        .unwrap();
+    assert_eq!(generated, "creator/1");
+    let generated = contract
+        .create_post_with_generated_id(&mut app, &creator2, "First", "Generated")
+        .unwrap();
+    assert_eq!(generated, "creator2/0");
+
+    let resp = contract
+        .query_hub_posts(&app, &creator, &creator, 1, 10)
+        .unwrap();
+    assert_eq!(resp[0].post.id, "creator/1");
+}
+
+#[test]
+fn test_migrate_likes_to_hub_scope() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let user = Addr::unchecked("user");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", &owner)
+            .unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "Channel", coin(0, XION))
+        .unwrap();
+    let post_id = Uuid::new_v4();
+    contract
+        .create_post(&mut app, &creator, post_id, "Hello World", "First")
+        .unwrap();
+
+    // likes used to be keyed by post id only
+    let legacy_likes: Map<&str, u64> = Map::new("likes");
+    let legacy_user_likes: Map<(&Addr, &str), bool> = Map::new("user_likes");
+    let post_id_str = post_id.to_string();
+    contract.set_raw_storage(&mut app, &legacy_likes.key(&post_id_str), b"3");
+    contract.set_raw_storage(
+        &mut app,
+        &legacy_user_likes.key((&user, &post_id_str)),
+        b"true",
+    );
+
+    contract.migrate(&mut app, &owner, code_id).unwrap();
+
+    assert_eq!(
+        contract.query_post_likes(&app, &creator, &post_id).unwrap(),
+        3
+    );
+    assert!(contract
+        .query_user_post_liked(&app, &user, &creator, &post_id)
+        .unwrap());
+    let err = contract
+        .like_post(&mut app, &user, &creator, &post_id)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PostAlreadyLiked {
+            id: post_id.to_string()
+        }
+    );
+}
+
+#[test]
+fn test_migrate_likes_of_shared_post_id() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let creator2 = Addr::unchecked("creator2");
+    let user = Addr::unchecked("user");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", &owner)
+            .unwrap();
+
+    // two legacy hubs with the same post id; creator2's post is the newer one
+    let post_id = Uuid::new_v4();
+    for (hub, updated) in [("creator", 1), ("creator2", 2)] {
+        let legacy_hub = format!(
+            r#"{{
+                "creator": "{hub}",
+                "name": "{hub} channel",
+                "payment": {{ "denom": "uxion", "amount": "0" }},
+                "subscribers": ["{hub}"],
+                "posts": [
+                    {{ "id": "{post_id}", "title": "{hub}", "content": "body", "updated": {updated} }}
+                ]
+            }}"#
+        );
+        contract.set_raw_storage(&mut app, &HUBS.key(hub), legacy_hub.as_bytes());
+    }
+    let legacy_likes: Map<&str, u64> = Map::new("likes");
+    let legacy_user_likes: Map<(&Addr, &str), bool> = Map::new("user_likes");
+    let post_id_str = post_id.to_string();
+    contract.set_raw_storage(&mut app, &legacy_likes.key(&post_id_str), b"3");
+    contract.set_raw_storage(
+        &mut app,
+        &legacy_user_likes.key((&user, &post_id_str)),
+        b"true",
+    );
+
+    contract.migrate(&mut app, &owner, code_id).unwrap();
+
+    // the likes go to the newest post only instead of being copied into both hubs
+    assert_eq!(
+        contract
+            .query_post_likes(&app, &creator2, &post_id)
+            .unwrap(),
+        3
+    );
+    assert!(contract
+        .query_user_post_liked(&app, &user, &creator2, &post_id)
+        .unwrap());
+    assert_eq!(
+        contract.query_post_likes(&app, &creator, &post_id).unwrap(),
+        0
+    );
+    assert!(!contract
+        .query_user_post_liked(&app, &user, &creator, &post_id)
+        .unwrap());
+}
+
+#[test]
+fn test_shared_post_ids_stay_in_their_hub() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let creator2 = Addr::unchecked("creator2");
+    let user = Addr::unchecked("user");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", &owner)
+            .unwrap();
+
+    // ids used to be unique per hub only, so two legacy hubs can share one
+    let post_id = Uuid::new_v4();
+    for (hub, updated) in [("creator", 1), ("creator2", 2)] {
+        let legacy_hub = format!(
+            r#"{{
+                "creator": "{hub}",
+                "name": "{hub} channel",
+                "payment": {{ "denom": "uxion", "amount": "0" }},
+                "subscribers": ["{hub}"],
+                "posts": [
+                    {{ "id": "{post_id}", "title": "{hub}", "content": "body", "updated": {updated} }}
+                ]
+            }}"#
+        );
+        contract.set_raw_storage(&mut app, &HUBS.key(hub), legacy_hub.as_bytes());
+    }
+    contract.migrate(&mut app, &owner, code_id).unwrap();
+
+    for hub in [&creator, &creator2] {
+        contract
+            .subscribe_to_hub(&mut app, &user, hub, &[])
+            .unwrap();
+        contract
+            .comment(&mut app, &user, hub, &post_id, None, hub.as_str())
+            .unwrap();
+    }
+
+    // each creator edits their own post, not the first one with the id
+    contract
+        .edit_post(&mut app, &creator2, &post_id, "Edited", "new body")
+        .unwrap();
+    let resp = contract
+        .query_hub_posts(&app, &creator2, &creator2, 1, 10)
+        .unwrap();
+    assert_eq!(resp[0].post.title, "Edited");
+    let resp = contract
+        .query_hub_posts(&app, &creator, &creator, 1, 10)
+        .unwrap();
+    assert_eq!(resp[0].post.title, "creator");
+
+    let resp = contract
+        .query_post_comments(&app, &creator2, &post_id, None, None)
+        .unwrap();
+    assert_eq!(resp.count, 1);
+    assert_eq!(resp.comments[0].comment.body, "creator2");
+
+    // deleting one hub's post leaves the other's data alone
+    contract.delete_post(&mut app, &creator, &post_id).unwrap();
+    let resp = contract
+        .query_post_comments(&app, &creator2, &post_id, None, None)
+        .unwrap();
+    assert_eq!(resp.count, 1);
+    let resp = contract
+        .query_post_revisions(&app, &creator2, &creator2, &post_id, None, None)
+        .unwrap();
+    assert_eq!(resp.len(), 1);
+    assert_eq!(resp[0].title, "creator2");
+}
+
+#[test]
+fn test_scheduled_posts() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let user = Addr::unchecked("user");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "Market Open", coin(0, XION))
+        .unwrap();
+    contract
+        .subscribe_to_hub(&mut app, &user, &creator, &[])
+        .unwrap();
+
+    let now = app.block_info().time.seconds();
+    let err = contract
+        .create_scheduled_post(&mut app, &creator, Uuid::new_v4(), "Late", "Too late", now)
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidPublishTime);
+
+    let opening = Uuid::new_v4();
+    let cancelled = Uuid::new_v4();
+    contract
+        .create_scheduled_post(&mut app, &creator, opening, "Opening", "Buy", now + 100)
+        .unwrap();
+    contract
+        .create_scheduled_post(&mut app, &creator, cancelled, "Closing", "Sell", now + 50)
+        .unwrap();
+
+    assert!(contract
+        .query_hub_posts(&app, &user, &creator, 1, 10)
+        .unwrap()
+        .is_empty());
+    assert!(contract
+        .query_scheduled_posts(&app, &user, &creator)
+        .unwrap()
+        .is_empty());
+    let resp = contract
+        .query_scheduled_posts(&app, &creator, &creator)
+        .unwrap();
+    let titles: Vec<_> = resp.iter().map(|post| post.title.as_str()).collect();
+    assert_eq!(titles, vec!["Closing", "Opening"]);
+
+    let err = contract
+        .reschedule_post(&mut app, &user, &opening, now + 200)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized);
+    contract
+        .reschedule_post(&mut app, &creator, &opening, now + 200)
+        .unwrap();
+    contract
+        .cancel_scheduled_post(&mut app, &creator, &cancelled)
+        .unwrap();
+    let resp = contract
+        .query_scheduled_posts(&app, &creator, &creator)
+        .unwrap();
+    assert_eq!(resp.len(), 1);
+    assert_eq!(resp[0].publish_at, Some(now + 200));
+
+    // queued posts can't be liked yet
+    let err = contract
+        .like_post(&mut app, &user, &creator, &opening)
+        .unwrap_err();
+    assert_eq!(err, ContractError::PostNotFound);
+
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    assert!(contract
+        .query_hub_posts(&app, &user, &creator, 1, 10)
+        .unwrap()
+        .is_empty());
+    contract
+        .create_post(&mut app, &creator, Uuid::new_v4(), "Premarket", "Flat")
+        .unwrap();
+
+    // the scheduled post goes on top of what was published while it was queued
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    let resp = contract
+        .query_hub_posts(&app, &user, &creator, 1, 10)
+        .unwrap();
+    let titles: Vec<_> = resp.iter().map(|post| post.post.title.as_str()).collect();
+    assert_eq!(titles, vec!["Opening", "Premarket"]);
+    contract
+        .like_post(&mut app, &user, &creator, &opening)
+        .unwrap();
+    assert!(contract
+        .query_scheduled_posts(&app, &creator, &creator)
+        .unwrap()
+        .is_empty());
+
+    let err = contract
+        .cancel_scheduled_post(&mut app, &creator, &opening)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PostNotScheduled {
+            id: opening.to_string()
+        }
+    );
+}
+
+#[test]
+fn test_off_chain_content() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "Research", coin(0, XION))
+        .unwrap();
+
+    let body = b"A long research report kept on IPFS";
+    let content_ref = ContentRef {
+        uri: "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string(),
+        sha256: sha256_hex(body),
+        size: body.len() as u64,
+    };
+
+    let invalid_refs = [
+        (
+            ContentRef {
+                uri: "ftp://example.com/report".to_string(),
+                ..content_ref.clone()
+            },
+            ContractError::InvalidUri {
+                uri: "ftp://example.com/report".to_string(),
+            },
+        ),
+        (
+            ContentRef {
+                sha256: "ABC".to_string(),
+                ..content_ref.clone()
+            },
+            ContractError::InvalidSha256 {
+                hash: "ABC".to_string(),
+            },
+        ),
+        (
+            ContentRef {
+                size: 0,
+                ..content_ref.clone()
+            },
+            ContractError::InvalidSize { size: 0 },
+        ),
+    ];
+    for (invalid_ref, expected) in invalid_refs {
+        let err = contract
+            .create_post_with(
+                &mut app,
+                &creator,
+                CreatePostMsg {
+                    title: "Report".to_string(),
+                    content_ref: Some(invalid_ref),
+                    ..Default::default()
+                },
+            )
+            .unwrap_err();
+        assert_eq!(err, expected);
+    }
+
+    let err = contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Report".to_string(),
+                content: "An inline body too".to_string(),
+                content_ref: Some(content_ref.clone()),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::ContentRefWithContent);
+
+    let post_id = Uuid::new_v4();
+    contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                post_id: Some(post_id.to_string()),
+                title: "Report".to_string(),
+                content_ref: Some(content_ref.clone()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    // edits can retitle an off-chain post but not give it an inline body
+    let err = contract
+        .edit_post(&mut app, &creator, &post_id, "Report", "An inline body")
+        .unwrap_err();
+    assert_eq!(err, ContractError::ContentRefWithContent);
+    contract
+        .edit_post(&mut app, &creator, &post_id, "Report (final)", "")
+        .unwrap();
+    let revisions = contract
+        .query_post_revisions(&app, &creator, &creator, &post_id, None, None)
+        .unwrap();
+    assert_eq!(revisions.len(), 1);
+    assert_eq!(revisions[0].title, "Report");
+    assert_eq!(revisions[0].content_ref, Some(content_ref.clone()));
+
+    let post = contract
+        .query_hub_posts(&app, &creator, &creator, 1, 1)
+        .unwrap()
+        .remove(0)
+        .post;
+    assert_eq!(post.content_ref, Some(content_ref.clone()));
+
+    // readers can check whatever a gateway served against the on-chain digest
+    assert_eq!(verify_post_content(&post, body), Ok(()));
+    assert_eq!(
+        verify_post_content(&post, b"A long research report kept on IPFX"),
+        Err(ContentError::HashMismatch {
+            expected: content_ref.sha256,
+            actual: sha256_hex(b"A long research report kept on IPFX"),
+        })
+    );
+    assert_eq!(
+        verify_post_content(&post, b"tampered"),
+        Err(ContentError::SizeMismatch {
+            expected: body.len() as u64,
+            actual: 8,
+        })
+    );
+}
+
+#[test]
+fn test_encrypted_premium_post() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let user = Addr::unchecked("user");
+    let outsider = Addr::unchecked("outsider");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "Premium", coin(0, XION))
+        .unwrap();
+    contract
+        .subscribe_to_hub(&mut app, &user, &creator, &[])
+        .unwrap();
+
+    let err = contract
+        .register_encryption_key(&mut app, &user, b"too short")
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidEncryptionKey);
+
+    let (user_secret, user_public) = generate_keypair(&mut OsRng);
+    contract
+        .register_encryption_key(&mut app, &user, &user_public)
+        .unwrap();
+    let registered = contract.query_encryption_key(&app, &user).unwrap().unwrap();
+    assert_eq!(registered.as_slice(), user_public);
+
+    let (content_key, ciphertext) = encrypt_post_body("Buy the dip", &mut OsRng);
+    let post_id = contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Alpha".to_string(),
+                content: ciphertext.clone(),
+                encrypted: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let public_key: [u8; 32] = registered.to_vec().try_into().unwrap();
+    let err = contract
+        .publish_post_keys(
+            &mut app,
+            &creator,
+            &post_id,
+            vec![PostKey {
+                subscriber: outsider.clone(),
+                envelope: wrap_content_key(&content_key, &public_key, &mut OsRng),
+            }],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotSubscribed {
+            addr: outsider.to_string()
+        }
+    );
+    contract
+        .publish_post_keys(
+            &mut app,
+            &creator,
+            &post_id,
+            vec![PostKey {
+                subscriber: user.clone(),
+                envelope: wrap_content_key(&content_key, &public_key, &mut OsRng),
+            }],
+        )
+        .unwrap();
+
+    let resp = contract
+        .query_hub_posts(&app, &outsider, &creator, 1, 10)
+        .unwrap();
+    assert!(resp[0].locked);
+    assert_eq!(resp[0].key_envelope, None);
+
+    let resp = contract
+        .query_hub_posts(&app, &user, &creator, 1, 10)
+        .unwrap()
+        .remove(0);
+    // state only ever holds ciphertext
+    assert_eq!(resp.post.content, ciphertext);
+    let envelope = resp.key_envelope.unwrap();
+    let unwrapped = unwrap_content_key(&envelope, &user_secret).unwrap();
+    assert_eq!(
+        decrypt_post_body(&unwrapped, &resp.post).unwrap(),
+        "Buy the dip"
+    );
+}
+
+#[test]
+fn test_post_summary_teasers() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let user = Addr::unchecked("user");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "Research", coin(0, XION))
+        .unwrap();
+    for i in 0..3 {
+        contract
+            .create_post_with(
+                &mut app,
+                &creator,
+                CreatePostMsg {
+                    title: format!("Report {}", i),
+                    summary: format!("What report {} covers", i),
+                    content: format!("Full report {}", i),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+    }
+
+    // the whole archive is browsable, page by page
+    let resp = contract
+        .query_hub_posts(&app, &user, &creator, 2, 2)
+        .unwrap();
+    assert_eq!(resp.len(), 1);
+    assert_eq!(resp[0].post.title, "Report 0");
+    assert_eq!(resp[0].post.summary, "What report 0 covers");
+    assert_eq!(resp[0].post.content, "");
+    assert!(resp[0].locked);
+
+    contract
+        .subscribe_to_hub(&mut app, &user, &creator, &[])
+        .unwrap();
+    let resp = contract
+        .query_hub_posts(&app, &user, &creator, 2, 2)
+        .unwrap();
+    assert_eq!(resp[0].post.summary, "What report 0 covers");
+    assert_eq!(resp[0].post.content, "Full report 0");
+    assert!(!resp[0].locked);
+}
+
+#[test]
+fn test_post_tags() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let other = Addr::unchecked("other");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "Rust", coin(0, XION))
+        .unwrap();
+    contract
+        .create_hub(&mut app, &other, "Go", coin(0, XION))
+        .unwrap();
+
+    let err = contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Bad".to_string(),
+                tags: vec!["no spaces".to_string()],
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidTagCharacter {
+            tag: "no spaces".to_string()
+        }
+    );
+    let err = contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Bad".to_string(),
+                tags: (0..6).map(|i| format!("tag-{}", i)).collect(),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::TooManyTags { max: 5 });
+
+    let first = Uuid::new_v4();
+    contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                post_id: Some(first.to_string()),
+                title: "Lifetimes".to_string(),
+                content: "Subscribers only".to_string(),
+                tags: vec![
+                    " Tutorial ".to_string(),
+                    "tutorial".to_string(),
+                    "rust".to_string(),
+                ],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let traits_post = contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Traits".to_string(),
+                tags: vec!["tutorial".to_string()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    contract
+        .create_post_with(
+            &mut app,
+            &other,
+            CreatePostMsg {
+                title: "Goroutines".to_string(),
+                tags: vec!["tutorial".to_string()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    // posts that aren't live yet don't count
+    let publish_at = app.block_info().time.seconds() + 100;
+    contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Macros".to_string(),
+                tags: vec!["rust".to_string(), "draft".to_string()],
+                publish_at: Some(publish_at),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        contract.query_hub_tags(&app, &creator).unwrap(),
+        vec![
+            TagCount {
+                tag: "rust".to_string(),
+                count: 1
+            },
+            TagCount {
+                tag: "tutorial".to_string(),
+                count: 2
+            },
+        ]
+    );
+
+    // grouped by hub, newest first within each hub, paginated
+    let mut titles = vec![];
+    let mut start_after = None;
+    loop {
+        let page = contract
+            .query_posts_by_tag(&app, "Tutorial", start_after, Some(2))
+            .unwrap();
+        let Some(last) = page.last() else { break };
+        start_after = Some((last.hub_addr.clone(), last.seq));
+        for tagged in &page {
+            assert!(tagged.post.locked);
+            assert_eq!(tagged.post.post.content, "");
+            titles.push(tagged.post.post.title.clone());
+        }
+    }
+    assert_eq!(titles.len(), 3);
+    assert!(titles.contains(&"Goroutines".to_string()));
+    let traits = titles.iter().position(|t| t == "Traits").unwrap();
+    assert_eq!(titles[traits + 1], "Lifetimes");
+
+    let lifetimes = contract
+        .query_posts_by_tag(&app, "rust", None, None)
+        .unwrap();
+    assert_eq!(lifetimes[0].post.post.tags, vec!["tutorial", "rust"]);
+
+    contract.delete_post(&mut app, &creator, &first).unwrap();
+    assert_eq!(
+        contract.query_hub_tags(&app, &creator).unwrap(),
+        vec![TagCount {
+            tag: "tutorial".to_string(),
+            count: 1
+        }]
+    );
+    assert_eq!(
+        contract
+            .query_posts_by_tag(&app, "tutorial", None, None)
+            .unwrap()
+            .len(),
+        2
+    );
+
+    // counts follow unpublishing, scheduled posts going out and posts expiring
+    contract
+        .unpublish_post(&mut app, &creator, &traits_post)
+        .unwrap();
+    assert!(contract.query_hub_tags(&app, &creator).unwrap().is_empty());
+    contract
+        .republish_post(&mut app, &creator, &traits_post)
+        .unwrap();
+    let expires_at = app.block_info().time.seconds() + 200;
+    contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Release notes".to_string(),
+                tags: vec!["news".to_string()],
+                expires_at: Some(expires_at),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    let tag = |tag: &str| TagCount {
+        tag: tag.to_string(),
+        count: 1,
+    };
+    assert_eq!(
+        contract.query_hub_tags(&app, &creator).unwrap(),
+        vec![tag("draft"), tag("news"), tag("rust"), tag("tutorial")]
+    );
+    assert_eq!(
+        contract
+            .query_hub_tags_page(&app, &creator, Some("news"), Some(1))
+            .unwrap(),
+        vec![tag("rust")]
+    );
+
+    app.update_block(|block| block.time = block.time.plus_seconds(100));
+    assert_eq!(
+        contract.query_hub_tags(&app, &creator).unwrap(),
+        vec![tag("draft"), tag("rust"), tag("tutorial")]
+    );
+}
+
+#[test]
+fn test_threaded_comments() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+    let stranger = Addr::unchecked("stranger");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "Forum", coin(0, XION))
+        .unwrap();
+    let post_id = Uuid::new_v4();
+    contract
+        .create_post(&mut app, &creator, post_id, "Ask me anything", "Go ahead")
+        .unwrap();
+    for user in [&alice, &bob] {
+        contract
+            .subscribe_to_hub(&mut app, user, &creator, &[])
+            .unwrap();
+    }
+
+    let err = contract
+        .comment(&mut app, &stranger, &creator, &post_id, None, "Hi")
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotSubscribed {
+            addr: stranger.to_string()
+        }
+    );
+    let err = contract
+        .comment(&mut app, &alice, &creator, &post_id, Some(42), "Hi")
+        .unwrap_err();
+    assert_eq!(err, ContractError::CommentNotFound { id: 42 });
+
+    let question = contract
+        .comment(&mut app, &alice, &creator, &post_id, None, "First question")
+        .unwrap();
+    let answer = contract
+        .comment(
+            &mut app,
+            &creator,
+            &creator,
+            &post_id,
+            Some(question),
+            "An answer",
+        )
+        .unwrap();
+    let follow_up = contract
+        .comment(
+            &mut app,
+            &alice,
+            &creator,
+            &post_id,
+            Some(answer),
+            "Follow-up",
+        )
+        .unwrap();
+    let spam = contract
+        .comment(&mut app, &bob, &creator, &post_id, None, "Spam")
+        .unwrap();
+    contract
+        .comment(&mut app, &bob, &creator, &post_id, None, "Second question")
+        .unwrap();
+
+    let resp = contract
+        .query_post_comments(&app, &creator, &post_id, None, Some(2))
+        .unwrap();
+    assert_eq!(resp.count, 5);
+    assert_eq!(resp.comments.len(), 2);
+    assert_eq!(resp.comments[0].comment.body, "First question");
+    assert!(resp.comments[0].has_replies);
+    assert_eq!(resp.comments[1].comment.id, spam);
+    assert!(!resp.comments[1].has_replies);
+
+    // replies are paged one level at a time
+    let resp = contract
+        .query_comment_replies(&app, &creator, &post_id, Some(question), None, None)
+        .unwrap();
+    assert_eq!(resp.comments.len(), 1);
+    assert_eq!(resp.comments[0].comment.author, creator);
+    let resp = contract
+        .query_comment_replies(&app, &creator, &post_id, Some(answer), None, None)
+        .unwrap();
+    assert_eq!(resp.comments[0].comment.id, follow_up);
+    assert!(!resp.comments[0].has_replies);
+    let resp = contract
+        .query_comment_replies(
+            &app,
+            &creator,
+            &post_id,
+            Some(answer),
+            Some(follow_up),
+            None,
+        )
+        .unwrap();
+    assert!(resp.comments.is_empty());
+
+    // moderation is up to the hub creator
+    let err = contract
+        .hide_comment(&mut app, &alice, &post_id, spam)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized);
+    contract
+        .hide_comment(&mut app, &creator, &post_id, spam)
+        .unwrap();
+    let resp = contract
+        .query_post_comments(&app, &creator, &post_id, Some(question), None)
+        .unwrap();
+    assert_eq!(resp.comments.len(), 2);
+    assert!(resp.comments[0].comment.hidden);
+    assert_eq!(resp.comments[0].comment.body, "");
+    assert_eq!(resp.comments[1].comment.body, "Second question");
+
+    // deleting a comment takes its replies with it
+    contract
+        .delete_comment(&mut app, &creator, &post_id, answer)
+        .unwrap();
+    let resp = contract
+        .query_post_comments(&app, &creator, &post_id, None, None)
+        .unwrap();
+    assert_eq!(resp.count, 3);
+    assert_eq!(resp.comments.len(), 3);
+    assert!(!resp.comments[0].has_replies);
+
+    contract.delete_post(&mut app, &creator, &post_id).unwrap();
+    let resp = contract
+        .query_post_comments(&app, &creator, &post_id, None, None)
+        .unwrap();
+    assert_eq!(resp.count, 0);
+    assert!(resp.comments.is_empty());
+}
+
+#[test]
+fn test_reposts() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let author = Addr::unchecked("author");
+    let curator = Addr::unchecked("curator");
+    let fan = Addr::unchecked("fan");
+    let reader = Addr::unchecked("reader");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &author, "Essays", coin(0, XION))
+        .unwrap();
+    contract
+        .create_hub(&mut app, &curator, "Picks", coin(0, XION))
+        .unwrap();
+    let original = contract
+        .create_post_with(
+            &mut app,
+            &author,
+            CreatePostMsg {
+                title: "On writing".to_string(),
+                summary: "Why drafts matter".to_string(),
+                content: "Premium essay".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let err = contract
+        .repost(&mut app, &fan, &author, &original, None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::HubNotFound {});
+    let err = contract
+        .repost(&mut app, &curator, &author, "missing", None)
+        .unwrap_err();
+    assert_eq!(err, ContractError::PostNotFound);
+    let err = contract
+        .repost(
+            &mut app,
+            &curator,
+            &author,
+            &original,
+            Some(&"x".repeat(501)),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidLength {
+            field: "summary".to_string(),
+            min: 0,
+            max: 500
+        }
+    );
+
+    let repost = contract
+        .repost(&mut app, &curator, &author, &original, Some("Must read"))
+        .unwrap();
+    // reposting the repost credits the original
+    contract
+        .create_hub(&mut app, &fan, "Fan picks", coin(0, XION))
+        .unwrap();
+    let second = contract
+        .repost(&mut app, &fan, &curator, &repost, None)
+        .unwrap();
+    assert_eq!(
+        contract
+            .query_post_reposts(&app, &author, &original)
+            .unwrap(),
+        2
+    );
+
+    // curator subscribers see the commentary, but the essay stays locked to author subscribers
+    contract
+        .subscribe_to_hub(&mut app, &reader, &curator, &[])
+        .unwrap();
+    let resp = contract
+        .query_hub_posts(&app, &reader, &curator, 1, 10)
+        .unwrap();
+    assert_eq!(resp[0].post.summary, "Must read");
+    assert!(!resp[0].locked);
+    let inline = resp[0].original.as_ref().unwrap();
+    assert_eq!(inline.post.title, "On writing");
+    assert_eq!(inline.post.summary, "Why drafts matter");
+    assert_eq!(inline.post.content, "");
+    assert!(inline.locked);
+
+    contract
+        .subscribe_to_hub(&mut app, &reader, &author, &[])
+        .unwrap();
+    let resp = contract
+        .query_hub_posts(&app, &reader, &curator, 1, 10)
+        .unwrap();
+    assert_eq!(
+        resp[0].original.as_ref().unwrap().post.content,
+        "Premium essay"
+    );
+
+    contract.delete_post(&mut app, &fan, &second).unwrap();
+    assert_eq!(
+        contract
+            .query_post_reposts(&app, &author, &original)
+            .unwrap(),
+        1
+    );
+    contract
+        .unpublish_post(&mut app, &author, &original)
+        .unwrap();
+    let resp = contract
+        .query_hub_posts(&app, &reader, &curator, 1, 10)
+        .unwrap();
+    assert_eq!(resp[0].original, None);
+}
+
+#[test]
+fn test_post_attachments() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let user = Addr::unchecked("user");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "Research", coin(0, XION))
+        .unwrap();
+
+    let chart = Attachment {
+        mime_type: "image/png".to_string(),
+        uri: "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string(),
+        sha256: sha256_hex(b"chart"),
+        size: 5,
+    };
+    let report = Attachment {
+        mime_type: "application/pdf".to_string(),
+        uri: "https://example.com/report.pdf".to_string(),
+        sha256: sha256_hex(b"report"),
+        size: 6,
+    };
+
+    let post = |attachments: Vec<Attachment>| CreatePostMsg {
+        title: "Q3 numbers".to_string(),
+        content: "Commentary".to_string(),
+        attachments,
+        ..Default::default()
+    };
+    let err = contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            post(vec![Attachment {
+                mime_type: "application/x-msdownload".to_string(),
+                ..chart.clone()
+            }]),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::UnsupportedMimeType {
+            mime_type: "application/x-msdownload".to_string()
+        }
+    );
+    let err = contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            post(vec![Attachment {
+                uri: "javascript:alert(1)".to_string(),
+                ..chart.clone()
+            }]),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidUri {
+            uri: "javascript:alert(1)".to_string()
+        }
+    );
+    let err = contract
+        .create_post_with(&mut app, &creator, post(vec![chart.clone(); 11]))
+        .unwrap_err();
+    assert_eq!(err, ContractError::TooManyAttachments { max: 10 });
+
+    contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            post(vec![chart.clone(), report.clone()]),
+        )
+        .unwrap();
+
+    let resp = contract
+        .query_hub_posts(&app, &user, &creator, 1, 10)
+        .unwrap();
+    assert!(resp[0].locked);
+    assert!(resp[0].post.attachments.is_empty());
+
+    contract
+        .subscribe_to_hub(&mut app, &user, &creator, &[])
+        .unwrap();
+    let resp = contract
+        .query_hub_posts(&app, &user, &creator, 1, 10)
+        .unwrap();
+    assert_eq!(resp[0].post.attachments, vec![chart, report]);
+}
+
+#[test]
+fn test_config_limits() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let admin = Addr::unchecked("admin");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    let config = contract.query_config(&app).unwrap();
+    assert_eq!(config.admin, owner);
+    assert_eq!(config.limits, Limits::default());
+
+    let err = contract
+        .create_hub(&mut app, &creator, "", coin(0, XION))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidLength {
+            field: "hub_name".to_string(),
+            min: 1,
+            max: 64
+        }
+    );
+    contract
+        .create_hub(&mut app, &creator, "Notes", coin(0, XION))
+        .unwrap();
+
+    let err = contract
+        .create_post(&mut app, &creator, Uuid::new_v4(), &"x".repeat(201), "")
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidLength {
+            field: "title".to_string(),
+            min: 1,
+            max: 200
+        }
+    );
+    let err = contract
+        .create_post(&mut app, &creator, Uuid::new_v4(), "Bell\u{7}", "")
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ControlCharacter {
+            field: "title".to_string()
+        }
+    );
+    let lines = Uuid::new_v4();
+    contract
+        .create_post(&mut app, &creator, lines, "Lines", "one\ntwo")
+        .unwrap();
+
+    let err = contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Teaser".to_string(),
+                summary: "x".repeat(501),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidLength {
+            field: "summary".to_string(),
+            min: 0,
+            max: 500
+        }
+    );
+    let err = contract
+        .edit_post_with_summary(
+            &mut app,
+            &creator,
+            &lines,
+            "Lines",
+            Some("Bell\u{7}"),
+            "one\ntwo",
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ControlCharacter {
+            field: "summary".to_string()
+        }
+    );
+    contract
+        .edit_post_with_summary(
+            &mut app,
+            &creator,
+            &lines,
+            "Lines",
+            Some("Two lines"),
+            "one\ntwo",
+        )
+        .unwrap();
+    contract
+        .edit_post_with_summary(&mut app, &creator, &lines, "Lines", None, "one\ntwo")
+        .unwrap();
+    // the teaser an edit replaced is kept with the revision
+    let revisions = contract
+        .query_post_revisions(&app, &creator, &creator, &lines, None, None)
+        .unwrap();
+    assert_eq!(revisions[0].summary, "");
+    assert_eq!(revisions[1].summary, "Two lines");
+
+    // comments go through the same checks
+    let err = contract
+        .comment(&mut app, &creator, &creator, &lines, None, "   ")
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidLength {
+            field: "comment".to_string(),
+            min: 1,
+            max: 2000
+        }
+    );
+    let err = contract
+        .comment(&mut app, &creator, &creator, &lines, None, "Bell\u{7}")
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ControlCharacter {
+            field: "comment".to_string()
+        }
+    );
+
+    // only the admin tunes the limits, and can hand the role over
+    let limits = Limits {
+        content: LengthLimit { min: 1, max: 10 },
+        comment: LengthLimit { min: 1, max: 10 },
+        max_posts_per_hub: 2,
+        ..Limits::default()
+    };
+    let err = contract
+        .update_config(&mut app, &creator, None, Some(limits.clone()))
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized);
+    let err = contract
+        .update_config(
+            &mut app,
+            &owner,
+            None,
+            Some(Limits {
+                title: LengthLimit { min: 5, max: 4 },
+                ..Limits::default()
+            }),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidLimit {
+            field: "title".to_string()
+        }
+    );
+    contract
+        .update_config(&mut app, &owner, Some(&admin), Some(limits.clone()))
+        .unwrap();
+    let config = contract.query_config(&app).unwrap();
+    assert_eq!(config.admin, admin);
+    assert_eq!(config.limits, limits);
+
+    let err = contract
+        .create_post(&mut app, &creator, Uuid::new_v4(), "Long", "far too long")
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidLength {
+            field: "content".to_string(),
+            min: 1,
+            max: 10
+        }
+    );
+    let err = contract
+        .comment(&mut app, &creator, &creator, &lines, None, "far too long")
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidLength {
+            field: "comment".to_string(),
+            min: 1,
+            max: 10
+        }
+    );
+
+    let second = Uuid::new_v4();
+    contract
+        .create_post(&mut app, &creator, second, "Second", "ok")
+        .unwrap();
+    let err = contract
+        .create_post(&mut app, &creator, Uuid::new_v4(), "Third", "ok")
+        .unwrap_err();
+    assert_eq!(err, ContractError::TooManyPosts { max: 2 });
+
+    // deleting a post frees its slot
+    contract.delete_post(&mut app, &creator, &second).unwrap();
+    contract
+        .create_post(&mut app, &creator, Uuid::new_v4(), "Third", "ok")
+        .unwrap();
+}
+
+#[test]
+fn test_pinned_posts() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let user = Addr::unchecked("user");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "Club", coin(0, XION))
+        .unwrap();
+    let ids: Vec<Uuid> = (0..6).map(|_| Uuid::new_v4()).collect();
+    for (i, id) in ids.iter().enumerate() {
+        contract
+            .create_post(&mut app, &creator, *id, &format!("Post {}", i), "Body")
+            .unwrap();
+    }
+
+    let err = contract.pin_post(&mut app, &user, &ids[0]).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized);
+    for id in &ids[..3] {
+        contract.pin_post(&mut app, &creator, id).unwrap();
+    }
+    let err = contract.pin_post(&mut app, &creator, &ids[0]).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PostAlreadyPinned {
+            id: ids[0].to_string()
+        }
+    );
+    let err = contract.pin_post(&mut app, &creator, &ids[3]).unwrap_err();
+    assert_eq!(err, ContractError::TooManyPinnedPosts { max: 3 });
+
+    // pinned posts lead page one as teasers, and are not repeated further down
+    let titles = |resp: &[PostResponse]| {
+        resp.iter()
+            .map(|view| (view.post.title.clone(), view.pinned))
+            .collect::<Vec<_>>()
+    };
+    let resp = contract
+        .query_hub_posts(&app, &user, &creator, 1, 2)
+        .unwrap();
+    assert_eq!(
+        titles(&resp),
+        vec![
+            ("Post 0".to_string(), true),
+            ("Post 1".to_string(), true),
+            ("Post 2".to_string(), true),
+            ("Post 5".to_string(), false),
+            ("Post 4".to_string(), false),
+        ]
+    );
+    assert!(resp[0].locked);
+    let resp = contract
+        .query_hub_posts(&app, &user, &creator, 2, 2)
+        .unwrap();
+    assert_eq!(titles(&resp), vec![("Post 3".to_string(), false)]);
+
+    contract.unpin_post(&mut app, &creator, &ids[1]).unwrap();
+    let err = contract
+        .unpin_post(&mut app, &creator, &ids[1])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PostNotPinned {
+            id: ids[1].to_string()
+        }
+    );
+    contract.delete_post(&mut app, &creator, &ids[0]).unwrap();
+    let resp = contract
+        .query_hub_posts(&app, &user, &creator, 1, 1)
+        .unwrap();
+    assert_eq!(
+        titles(&resp),
+        vec![("Post 2".to_string(), true), ("Post 5".to_string(), false)]
+    );
+}
+
+#[test]
+fn test_public_posts() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let visitor = Addr::unchecked("visitor");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "Newsletter", coin(100, XION))
+        .unwrap();
+    contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Free sample".to_string(),
+                content: "Read all about it".to_string(),
+                visibility: Visibility::Public,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    contract
+        .create_post(&mut app, &creator, Uuid::new_v4(), "Members", "Paid")
+        .unwrap();
+    contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Another sample".to_string(),
+                content: "Also free".to_string(),
+                visibility: Visibility::Public,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    // each post decides for itself, across every page
+    let resp = contract
+        .query_hub_posts(&app, &visitor, &creator, 1, 2)
+        .unwrap();
+    assert_eq!(resp[0].post.content, "Also free");
+    assert!(!resp[0].locked);
+    assert_eq!(resp[1].post.content, "");
+    assert!(resp[1].locked);
+    let resp = contract
+        .query_hub_posts(&app, &visitor, &creator, 2, 2)
+        .unwrap();
+    assert_eq!(resp[0].post.content, "Read all about it");
+    assert_eq!(resp[0].post.visibility, Visibility::Public);
+    assert!(!resp[0].locked);
+}
+
+#[test]
+fn test_post_series() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let other = Addr::unchecked("other");
+    let user = Addr::unchecked("user");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "Research", coin(0, XION))
+        .unwrap();
+    contract
+        .create_hub(&mut app, &other, "Elsewhere", coin(0, XION))
+        .unwrap();
+    let parts: Vec<String> = (1..=4)
+        .map(|part| {
+            contract
+                .create_post_with(
+                    &mut app,
+                    &creator,
+                    CreatePostMsg {
+                        title: format!("Part {}", part),
+                        content: format!("Body {}", part),
+                        ..Default::default()
+                    },
+                )
+                .unwrap()
+        })
+        .collect();
+    let foreign = contract
+        .create_post_with(
+            &mut app,
+            &other,
+            CreatePostMsg {
+                title: "Not mine".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let err = contract
+        .create_series(&mut app, &creator, "Deep dive", &[&parts[0], &foreign])
+        .unwrap_err();
+    assert_eq!(err, ContractError::PostNotFound);
+    let series_id = contract
+        .create_series(&mut app, &creator, "Deep dive", &[&parts[0], &parts[1]])
+        .unwrap();
+    let err = contract
+        .append_to_series(&mut app, &creator, series_id, &parts[1])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PostAlreadyInSeries {
+            id: parts[1].clone()
+        }
+    );
+    let err = contract
+        .append_to_series(&mut app, &other, series_id, &foreign)
+        .unwrap_err();
+    assert_eq!(err, ContractError::SeriesNotFound { id: series_id });
+    contract
+        .append_to_series(&mut app, &creator, series_id, &parts[2])
+        .unwrap();
+    contract
+        .append_to_series(&mut app, &creator, series_id, &parts[3])
+        .unwrap();
+
+    let err = contract
+        .reorder_series(&mut app, &creator, series_id, &[&parts[0], &parts[1]])
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidSeriesOrder);
+    contract
+        .reorder_series(
+            &mut app,
+            &creator,
+            series_id,
+            &[&parts[0], &parts[2], &parts[1], &parts[3]],
+        )
+        .unwrap();
+    contract
+        .remove_from_series(&mut app, &creator, series_id, &parts[3])
+        .unwrap();
+
+    let series = contract.query_hub_series(&app, &creator, &creator).unwrap();
+    assert_eq!(series.len(), 1);
+    assert_eq!(series[0].title, "Deep dive");
+
+    // titles in order for everyone, bodies for subscribers
+    let resp = contract
+        .query_series(&app, &user, &creator, series_id)
+        .unwrap();
+    let titles: Vec<_> = resp.posts.iter().map(|view| &view.post.title).collect();
+    assert_eq!(titles, ["Part 1", "Part 3", "Part 2"]);
+    assert!(resp.posts.iter().all(|view| view.locked));
+    contract
+        .subscribe_to_hub(&mut app, &user, &creator, &[])
+        .unwrap();
+    let resp = contract
+        .query_series(&app, &user, &creator, series_id)
+        .unwrap();
+    assert_eq!(resp.posts[1].post.content, "Body 3");
+
+    // deleted posts drop out of their series
+    contract.delete_post(&mut app, &creator, &parts[2]).unwrap();
+    let series = contract.query_hub_series(&app, &creator, &creator).unwrap();
+    assert_eq!(series[0].post_ids, vec![parts[0].clone(), parts[1].clone()]);
+
+    // and unpublished ones are only listed to the creator
+    contract
+        .unpublish_post(&mut app, &creator, &parts[1])
+        .unwrap();
+    let series = contract.query_hub_series(&app, &user, &creator).unwrap();
+    assert_eq!(series[0].post_ids, vec![parts[0].clone()]);
+    let series = contract.query_hub_series(&app, &creator, &creator).unwrap();
+    assert_eq!(series[0].post_ids, vec![parts[0].clone(), parts[1].clone()]);
+
+    contract
+        .delete_series(&mut app, &creator, series_id)
+        .unwrap();
+    assert!(contract
+        .query_hub_series(&app, &creator, &creator)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_post_polls() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+    let stranger = Addr::unchecked("stranger");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "Markets", coin(0, XION))
+        .unwrap();
+    for user in [&alice, &bob] {
+        contract
+            .subscribe_to_hub(&mut app, user, &creator, &[])
+            .unwrap();
+    }
+
+    let now = app.block_info().time.seconds();
+    let poll = Poll {
+        question: "Where is BTC in 30 days?".to_string(),
+        options: vec!["Higher".to_string(), "Lower".to_string()],
+        closes_at: now + 3600,
+        hide_results: true,
+    };
+    let err = contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Sentiment".to_string(),
+                poll: Some(Poll {
+                    options: vec!["Only one".to_string()],
+                    ..poll.clone()
+                }),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidPoll);
+    let err = contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Sentiment".to_string(),
+                poll: Some(Poll {
+                    options: vec!["Higher".to_string(), "x".repeat(201)],
+                    ..poll.clone()
+                }),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidLength {
+            field: "poll_option".to_string(),
+            min: 1,
+            max: 200
+        }
+    );
+    let err = contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Sentiment".to_string(),
+                poll: Some(Poll {
+                    question: "Where\nis BTC?".to_string(),
+                    ..poll.clone()
+                }),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ControlCharacter {
+            field: "poll_question".to_string()
+        }
+    );
+    let post_id = contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Sentiment".to_string(),
+                poll: Some(poll),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let err = contract
+        .vote_poll(&mut app, &stranger, &creator, &post_id, 0)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotSubscribed {
+            addr: stranger.to_string()
+        }
+    );
+    let err = contract
+        .vote_poll(&mut app, &alice, &creator, &post_id, 2)
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidPollOption { option: 2 });
+    contract
+        .vote_poll(&mut app, &alice, &creator, &post_id, 0)
+        .unwrap();
+    let err = contract
+        .vote_poll(&mut app, &alice, &creator, &post_id, 1)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::AlreadyVoted {
+            id: post_id.clone()
+        }
+    );
+    contract
+        .vote_poll(&mut app, &bob, &creator, &post_id, 0)
+        .unwrap();
+    assert_eq!(
+        contract
+            .query_user_poll_vote(&app, &alice, &creator, &post_id)
+            .unwrap(),
+        Some(0)
+    );
+    assert_eq!(
+        contract
+            .query_user_poll_vote(&app, &stranger, &creator, &post_id)
+            .unwrap(),
+        None
+    );
+
+    // tallies stay hidden until the poll closes
+    let resp = contract
+        .query_poll_results(&app, &creator, &post_id)
+        .unwrap();
+    assert!(!resp.closed);
+    assert_eq!(resp.tallies, None);
+
+    app.update_block(|block| block.time = block.time.plus_seconds(3600));
+    let err = contract
+        .vote_poll(&mut app, &creator, &creator, &post_id, 1)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PollClosed {
+            id: post_id.clone()
+        }
+    );
+    let resp = contract
+        .query_poll_results(&app, &creator, &post_id)
+        .unwrap();
+    assert!(resp.closed);
+    assert_eq!(resp.tallies, Some(vec![2, 0]));
+
+    // polls of posts that aren't out yet stay out of sight
+    let publish_at = app.block_info().time.seconds() + 3600;
+    let scheduled = contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Next month".to_string(),
+                publish_at: Some(publish_at),
+                poll: Some(Poll {
+                    question: "Where is ETH in 30 days?".to_string(),
+                    options: vec!["Higher".to_string(), "Lower".to_string()],
+                    closes_at: publish_at + 3600,
+                    hide_results: false,
+                }),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert!(contract
+        .query_poll_results(&app, &creator, &scheduled)
+        .is_err());
+    app.update_block(|block| block.time = block.time.plus_seconds(3600));
+    let resp = contract
+        .query_poll_results(&app, &creator, &scheduled)
+        .unwrap();
+    assert_eq!(resp.poll.question, "Where is ETH in 30 days?");
+}
+
+#[test]
+fn test_guest_authors() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let other = Addr::unchecked("other");
+    let guest = Addr::unchecked("guest");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "Insights", coin(0, XION))
+        .unwrap();
+    contract
+        .create_hub(&mut app, &other, "Digest", coin(0, XION))
+        .unwrap();
+
+    let guest_post = |hub_addr: &Addr, title: &str| CreatePostMsg {
+        title: title.to_string(),
+        content: "Guest body".to_string(),
+        hub_addr: Some(hub_addr.clone()),
+        ..Default::default()
+    };
+    let err = contract
+        .create_post_with(&mut app, &guest, guest_post(&creator, "Uninvited"))
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized);
+
+    contract.add_author(&mut app, &creator, &guest).unwrap();
+    contract.add_author(&mut app, &other, &guest).unwrap();
+    contract
+        .create_post_with(&mut app, &guest, guest_post(&creator, "Guest insight"))
+        .unwrap();
+    contract
+        .create_post_with(&mut app, &guest, guest_post(&other, "Guest digest"))
+        .unwrap();
+    contract
+        .create_post(&mut app, &creator, Uuid::new_v4(), "Own post", "Body")
+        .unwrap();
+
+    let resp = contract
+        .query_hub_posts(&app, &creator, &creator, 1, 10)
+        .unwrap();
+    let authors: Vec<_> = resp
+        .iter()
+        .map(|view| view.post.author.clone().unwrap())
+        .collect();
+    assert_eq!(authors, vec![creator.clone(), guest.clone()]);
+
+    // an author's posts across every hub they write for
+    let resp = contract
+        .query_posts_by_author(&app, &guest, None, None)
+        .unwrap();
+    let mut titles: Vec<_> = resp
+        .iter()
+        .map(|listing| &listing.post.post.title)
+        .collect();
+    titles.sort();
+    assert_eq!(titles, ["Guest digest", "Guest insight"]);
+    assert!(resp.iter().all(|listing| listing.post.locked));
+    let resp = contract
+        .query_posts_by_author(&app, &guest, None, Some(1))
+        .unwrap();
+    let last = &resp[0];
+    let resp = contract
+        .query_posts_by_author(&app, &guest, Some((last.hub_addr.clone(), last.seq)), None)
+        .unwrap();
+    assert_eq!(resp.len(), 1);
+
+    contract.remove_author(&mut app, &creator, &guest).unwrap();
+    let err = contract
+        .remove_author(&mut app, &creator, &guest)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotAnAuthor {
+            addr: guest.to_string()
+        }
+    );
+    let err = contract
+        .create_post_with(&mut app, &guest, guest_post(&creator, "Again"))
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized);
+}
+
+#[test]
+fn test_expiring_posts() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let user = Addr::unchecked("user");
+    let anyone = Addr::unchecked("anyone");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "Alpha", coin(0, XION))
+        .unwrap();
+    contract
+        .subscribe_to_hub(&mut app, &user, &creator, &[])
+        .unwrap();
+
+    let now = app.block_info().time.seconds();
+    let err = contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Stale".to_string(),
+                expires_at: Some(now),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidExpiry);
+
+    let ephemeral = |title: &str, expires_at: u64| CreatePostMsg {
+        post_id: Some(title.to_lowercase()),
+        title: title.to_string(),
+        expires_at: Some(expires_at),
+        ..Default::default()
+    };
+    contract
+        .create_post_with(&mut app, &creator, ephemeral("Hourly", now + 3600))
+        .unwrap();
+    contract
+        .create_post_with(&mut app, &creator, ephemeral("Daily", now + 86400))
+        .unwrap();
+    contract
+        .create_post(&mut app, &creator, Uuid::new_v4(), "Evergreen", "Body")
+        .unwrap();
+    contract
+        .like_post(&mut app, &user, &creator, "hourly")
+        .unwrap();
+
+    let titles = |app: &App| {
+        contract
+            .query_hub_posts(app, &creator, &creator, 1, 10)
+            .unwrap()
+            .into_iter()
+            .map(|view| view.post.title)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(titles(&app), ["Evergreen", "Daily", "Hourly"]);
+
+    // expired posts vanish from queries right away, even for the creator
+    app.update_block(|block| block.time = block.time.plus_seconds(3600));
+    assert_eq!(titles(&app), ["Evergreen", "Daily"]);
+    assert!(contract
+        .query_user_post_liked(&app, &user, &creator, "hourly")
+        .unwrap());
+
+    // and anyone can reclaim their storage afterwards
+    contract
+        .prune_expired_posts(&mut app, &anyone, &creator, None)
+        .unwrap();
+    assert!(!contract
+        .query_user_post_liked(&app, &user, &creator, "hourly")
+        .unwrap());
+    assert!(contract.query_post_likes(&app, &creator, "hourly").is_err());
+
+    app.update_block(|block| block.time = block.time.plus_seconds(86400));
+    contract
+        .prune_expired_posts(&mut app, &anyone, &creator, Some(1))
+        .unwrap();
+    assert_eq!(titles(&app), ["Evergreen"]);
+    assert!(contract.query_post_likes(&app, &creator, "daily").is_err());
+}
+
+#[test]
+fn test_post_language_variants() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let user = Addr::unchecked("user");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "Global", coin(0, XION))
+        .unwrap();
+
+    let variant = |lang: &str, title: &str, content: &str| PostVariant {
+        lang: lang.to_string(),
+        title: title.to_string(),
+        content: content.to_string(),
+    };
+    let err = contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Hello".to_string(),
+                variants: vec![variant("ko", "안녕하세요", "본문")],
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::MissingDefaultLanguage);
+    let err = contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Hello".to_string(),
+                lang: Some("en".to_string()),
+                variants: vec![variant("zh_Hans", "你好", "正文")],
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidLanguageTag {
+            tag: "zh_Hans".to_string()
+        }
+    );
+    let err = contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Hello".to_string(),
+                lang: Some("en".to_string()),
+                variants: vec![variant("EN", "Hi", "Body")],
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DuplicateLanguage {
+            lang: "EN".to_string()
+        }
+    );
+
+    contract
+        .create_post_with(
+            &mut app,
+            &creator,
+            CreatePostMsg {
+                title: "Hello".to_string(),
+                content: "Body".to_string(),
+                lang: Some("en".to_string()),
+                variants: vec![
+                    variant("zh-Hans", "你好", "正文"),
+                    variant("ko", "안녕하세요", "본문"),
+                ],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    contract
+        .create_post(&mut app, &creator, Uuid::new_v4(), "English only", "Text")
+        .unwrap();
+
+    contract
+        .subscribe_to_hub(&mut app, &user, &creator, &[])
+        .unwrap();
+    let resp = contract
+        .query_hub_posts_in(&app, &user, &creator, 1, 10, Some("zh-hans"))
+        .unwrap();
+    assert_eq!(resp[0].post.title, "English only");
+    assert_eq!(resp[1].post.title, "你好");
+    assert_eq!(resp[1].post.content, "正文");
+    assert_eq!(resp[1].post.lang.as_deref(), Some("zh-Hans"));
+
+    // unknown languages fall back to the default, and pages are unchanged
+    let resp = contract
+        .query_hub_posts_in(&app, &user, &creator, 2, 1, Some("fr"))
+        .unwrap();
+    assert_eq!(resp[0].post.title, "Hello");
+    assert_eq!(resp[0].post.content, "Body");
+
+    // non-subscribers get translated teasers only
+    let resp = contract
+        .query_hub_posts_in(&app, &owner, &creator, 2, 1, Some("ko"))
+        .unwrap();
+    assert_eq!(resp[0].post.title, "안녕하세요");
+    assert_eq!(resp[0].post.content, "");
+    assert!(resp[0].post.variants.iter().all(|v| v.content.is_empty()));
+}
+
+#[test]
+fn test_unlike_post() {
+    let mut app = App::default();
+    let owner = Addr::unchecked("owner");
+    let creator = Addr::unchecked("creator");
+    let alice = Addr::unchecked("alice");
+    let bob = Addr::unchecked("bob");
+
+    let code_id = XionHubContract::store_code(&mut app);
+    let contract =
+        XionHubContract::instantiate(&mut app, code_id, &owner, "XionHub contract", None).unwrap();
+
+    contract
+        .create_hub(&mut app, &creator, "Likes", coin(0, XION))
+        .unwrap();
+    let post_id = Uuid::new_v4();
+    contract
+        .create_post(&mut app, &creator, post_id, "Title", "Body")
+        .unwrap();
+
+    // nothing to take back before liking, so the count can't underflow
+    let err = contract
+        .unlike_post(&mut app, &alice, &creator, &post_id)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PostNotLiked {
+            id: post_id.to_string()
+        }
+    );
+    assert_eq!(
+        contract.query_post_likes(&app, &creator, &post_id).unwrap(),
+        0
+    );
+
+    contract
+        .like_post(&mut app, &alice, &creator, &post_id)
+        .unwrap();
+    contract
+        .like_post(&mut app, &bob, &creator, &post_id)
+        .unwrap();
+    contract
+        .unlike_post(&mut app, &alice, &creator, &post_id)
+        .unwrap();
+    assert_eq!(
+        contract.query_post_likes(&app, &creator, &post_id).unwrap(),
+        1
+    );
+    assert!(!contract
+        .query_user_post_liked(&app, &alice, &creator, &post_id)
+        .unwrap());
+
+    // a second unlike is rejected rather than decrementing bob's like
+    let err = contract
+        .unlike_post(&mut app, &alice, &creator, &post_id)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PostNotLiked {
+            id: post_id.to_string()
+        }
+    );
+    assert_eq!(
+        contract.query_post_likes(&app, &creator, &post_id).unwrap(),
+        1
+    );
+
+    // and the like can be given again
+    contract
+        .like_post(&mut app, &alice, &creator, &post_id)
+        .unwrap();
+    assert_eq!(
+        contract.query_post_likes(&app, &creator, &post_id).unwrap(),
+        2
+    );
+}
@@ -1,6 +1,12 @@
 use cosmwasm_std::Addr;
+use cosmwasm_std::Binary;
 use cosmwasm_std::Coin;
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::Decimal;
+use cosmwasm_std::StdError;
+use cosmwasm_std::StdResult;
+use cosmwasm_std::Uint128;
+use cosmwasm_std::{Order, Storage};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -8,8 +14,149 @@ use serde::{Deserialize, Serialize};
 pub struct Post {
     pub id: String,
     pub title: String,
+    /// Public teaser shown to everyone, unlike the premium `content`.
+    #[serde(default)]
+    pub summary: String,
     pub content: String,
     pub updated: u64,
+    /// Hidden from everyone but the hub creator until republished.
+    #[serde(default)]
+    pub unpublished: bool,
+    /// Block time (seconds) before which only the hub creator can see the post.
+    #[serde(default)]
+    pub publish_at: Option<u64>,
+    /// Block time (seconds) the post went or goes live, which orders the hub's feed; zero for
+    /// posts from before scheduling.
+    #[serde(default)]
+    pub published_at: u64,
+    /// Where the body lives when it is kept off-chain.
+    #[serde(default)]
+    pub content_ref: Option<ContentRef>,
+    /// `content` is base64 ciphertext; subscribers get the key through `POST_KEYS`.
+    #[serde(default)]
+    pub encrypted: bool,
+    /// Normalized (lowercase) topic tags.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Set on reposts, whose `summary` holds the reposter's commentary.
+    #[serde(default)]
+    pub repost_of: Option<RepostRef>,
+    /// Media referenced by the post, gated like `content`.
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    #[serde(default)]
+    pub visibility: Visibility,
+    /// Poll subscribers can vote on until `closes_at`.
+    #[serde(default)]
+    pub poll: Option<Poll>,
+    /// Who wrote the post: the hub creator or one of the hub's authors.
+    #[serde(default)]
+    pub author: Option<Addr>,
+    /// Block time (seconds) from which the post is hidden and can be pruned.
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    /// BCP-47 tag of `title` and `content`, the default variant.
+    #[serde(default)]
+    pub lang: Option<String>,
+    /// Translations of the post besides the default.
+    #[serde(default)]
+    pub variants: Vec<PostVariant>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PostVariant {
+    /// BCP-47 language tag, e.g. `zh-Hans` or `ko`.
+    pub lang: String,
+    pub title: String,
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Poll {
+    pub question: String,
+    pub options: Vec<String>,
+    /// Block time (seconds) after which no more votes are taken.
+    pub closes_at: u64,
+    /// Keep tallies private until the poll closes.
+    #[serde(default)]
+    pub hide_results: bool,
+}
+
+impl Poll {
+    pub fn is_closed(&self, now: u64) -> bool {
+        now >= self.closes_at
+    }
+}
+
+/// Who can read a post's body and attachments; everyone sees the title and summary.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    Public,
+    #[default]
+    Subscribers,
+}
+
+/// The original post a repost points at.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RepostRef {
+    pub hub_addr: String,
+    pub post_id: String,
+}
+
+/// Pointer to an off-chain body, with enough to check what a gateway serves.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ContentRef {
+    /// `ipfs://<cid>` or `https://...`
+    pub uri: String,
+    /// Lowercase hex sha256 of the body bytes.
+    pub sha256: String,
+    /// Body length in bytes.
+    pub size: u64,
+}
+
+/// Metadata of a file attached to a post; the file itself lives off-chain.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Attachment {
+    pub mime_type: String,
+    /// `ipfs://<cid>` or `https://...`
+    pub uri: String,
+    /// Lowercase hex sha256 of the file bytes.
+    pub sha256: String,
+    /// File length in bytes.
+    pub size: u64,
+}
+
+impl Post {
+    pub fn is_scheduled(&self, now: u64) -> bool {
+        matches!(self.publish_at, Some(publish_at) if publish_at > now)
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        matches!(self.expires_at, Some(expires_at) if expires_at <= now)
+    }
+
+    /// Swaps the variant in `lang` into `title` and `content`, keeping the default among the
+    /// variants; leaves the post as is when there is no such translation.
+    pub fn select_variant(&mut self, lang: &str) {
+        let Some(default_lang) = self.lang.as_mut() else {
+            return;
+        };
+        if let Some(variant) = self
+            .variants
+            .iter_mut()
+            .find(|variant| variant.lang.eq_ignore_ascii_case(lang))
+        {
+            std::mem::swap(default_lang, &mut variant.lang);
+            std::mem::swap(&mut self.title, &mut variant.title);
+            std::mem::swap(&mut self.content, &mut variant.content);
+        }
+    }
+
+    /// Whether readers other than the hub creator can see the post.
+    pub fn is_live(&self, now: u64) -> bool {
+        !self.unpublished && !self.is_scheduled(now) && !self.is_expired(now)
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -18,12 +165,294 @@ pub struct Hub {
     pub name: String,
     pub payment: Coin,
     pub subscribers: Vec<Addr>,
-    pub posts: Vec<Post>,
+    /// Treat `payment` as a minimum and record anything above it as a contribution.
+    #[serde(default)]
+    pub pay_what_you_want: bool,
+    /// Prices the subscription from the subscriber count instead of `payment.amount`.
+    #[serde(default)]
+    pub price_curve: Option<PriceCurve>,
+}
+
+impl Hub {
+    /// Price of the next subscription, in the hub's payment denom.
+    pub fn subscription_price(&self) -> StdResult<Coin> {
+        let amount = match &self.price_curve {
+            None => self.payment.amount,
+            Some(curve) => {
+                // The creator is always the first subscriber and never pays
+                let subscribers = self.subscribers.len().saturating_sub(1) as u64;
+                curve.price(subscribers)?
+            }
+        };
+        Ok(Coin::new(amount.u128(), self.payment.denom.clone()))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceCurve {
+    /// `base + slope * subscribers`
+    Linear { base: Uint128, slope: Uint128 },
+    /// `base * (1 + growth_bps / 10000) ^ subscribers`, rounded down
+    Exponential { base: Uint128, growth_bps: u64 },
+}
+
+impl PriceCurve {
+    pub fn price(&self, subscribers: u64) -> StdResult<Uint128> {
+        match self {
+            PriceCurve::Linear { base, slope } => {
+                Ok(base.checked_add(slope.checked_mul(subscribers.into())?)?)
+            }
+            PriceCurve::Exponential { base, growth_bps } => {
+                let exp = u32::try_from(subscribers)
+                    .map_err(|_| StdError::generic_err("Too many subscribers to price"))?;
+                let factor = (Decimal::one() + Decimal::bps(*growth_bps)).checked_pow(exp)?;
+                base.checked_multiply_ratio(factor.atomics(), Decimal::one().atomics())
+                    .map_err(|err| StdError::generic_err(err.to_string()))
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SponsorPool {
+    pub id: u64,
+    pub sponsor: Addr,
+    pub hub_addr: Addr,
+    pub funds: Coin,
+    /// When set, only these addresses may claim a subscription from the pool.
+    pub beneficiaries: Option<Vec<Addr>>,
+    /// Block time (seconds) after which claims stop and the sponsor can reclaim the rest.
+    pub expires_at: u64,
+}
+
+pub struct PostIndexes<'a> {
+    pub id: MultiIndex<'a, String, Post, (&'a str, u64)>,
+    pub updated: MultiIndex<'a, u64, Post, (&'a str, u64)>,
+    pub author: MultiIndex<'a, String, Post, (&'a str, u64)>,
+}
+
+impl<'a> IndexList<Post> for PostIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Post>> + '_> {
+        let v: Vec<&dyn Index<Post>> = vec![&self.id, &self.updated, &self.author];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Posts keyed by (hub address, sequence), so a hub's posts can be paged newest first.
+pub fn posts<'a>() -> IndexedMap<'a, (&'a str, u64), Post, PostIndexes<'a>> {
+    let indexes = PostIndexes {
+        id: MultiIndex::new(|_pk, post| post.id.clone(), "posts", "posts__id"),
+        updated: MultiIndex::new(|_pk, post| post.updated, "posts", "posts__updated"),
+        author: MultiIndex::new(
+            |_pk, post| {
+                post.author
+                    .as_ref()
+                    .map(Addr::to_string)
+                    .unwrap_or_default()
+            },
+            "posts",
+            "posts__author",
+        ),
+    };
+    IndexedMap::new("posts", indexes)
+}
+
+/// Looks a post up by id, returning its (hub address, sequence) key alongside it.
+pub fn find_post(storage: &dyn Storage, post_id: &str) -> StdResult<Option<((String, u64), Post)>> {
+    posts()
+        .idx
+        .id
+        .prefix(post_id.to_string())
+        .range(storage, None, None, Order::Ascending)
+        .next()
+        .transpose()
+}
+
+/// Looks a post up by id within one hub.
+pub fn find_hub_post(
+    storage: &dyn Storage,
+    hub_addr: &str,
+    post_id: &str,
+) -> StdResult<Option<(u64, Post)>> {
+    // Ids were not unique across hubs before, so legacy posts may share one
+    for item in
+        posts()
+            .idx
+            .id
+            .prefix(post_id.to_string())
+            .range(storage, None, None, Order::Ascending)
+    {
+        let ((hub, seq), post) = item?;
+        if hub == hub_addr {
+            return Ok(Some((seq, post)));
+        }
+    }
+    Ok(None)
+}
+
+/// Sequence number the next post of each hub is saved under.
+pub const POST_SEQ: Map<&str, u64> = Map::new("post_seq");
+
+/// A superseded version of a post, kept so readers can see what an edit changed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PostRevision {
+    pub revision: u64,
+    pub title: String,
+    #[serde(default)]
+    pub summary: String,
+    pub content: String,
+    #[serde(default)]
+    pub content_ref: Option<ContentRef>,
+    pub updated: u64,
+}
+
+/// Keyed by (hub, post id, revision).
+pub const POST_REVISIONS: Map<(&str, &str, u64), PostRevision> = Map::new("post_revisions");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Comment {
+    pub id: u64,
+    pub author: Addr,
+    /// The comment this one replies to, `None` for top-level comments.
+    pub parent_id: Option<u64>,
+    pub body: String,
+    pub created: u64,
+    /// Hidden by the hub creator; the body is withheld from queries.
+    pub hidden: bool,
+}
+
+/// Comments keyed by (hub, post id, comment id).
+pub const COMMENTS: Map<(&str, &str, u64), Comment> = Map::new("comments");
+/// Next comment id per (hub, post id).
+pub const COMMENT_SEQ: Map<(&str, &str), u64> = Map::new("comment_seq");
+/// Number of comments per (hub, post id), hidden ones included.
+pub const COMMENT_COUNTS: Map<(&str, &str), u64> = Map::new("comment_counts");
+/// Comment ids keyed by (hub, post id, (parent id, comment id)), with top-level comments under
+/// parent 0 since ids start at 1.
+pub const COMMENT_THREADS: Map<(&str, &str, (u64, u64)), bool> = Map::new("comment_threads");
+
+/// Ids of the replies to `parent_id` (0 for top-level comments), oldest first.
+pub fn comment_replies<'a>(
+    storage: &'a dyn Storage,
+    hub_addr: &'a str,
+    post_id: &'a str,
+    parent_id: u64,
+    start_after: Option<u64>,
+) -> Box<dyn Iterator<Item = StdResult<u64>> + 'a> {
+    let start = Bound::exclusive((parent_id, start_after.unwrap_or_default()));
+    let end = Bound::inclusive((parent_id, u64::MAX));
+    Box::new(
+        COMMENT_THREADS
+            .prefix((hub_addr, post_id))
+            .keys(storage, Some(start), Some(end), Order::Ascending)
+            .map(|key| key.map(|(_, id)| id)),
+    )
 }
 
 pub const HUBS: Map<&str, Hub> = Map::new("hubs");
 pub const SUBSCRIPTIONS: Map<(&Addr, &str), bool> = Map::new("subscriptions");
 
 pub const HUB_ADDRESS: Item<Vec<String>> = Item::new("hub_address");
-pub const LIKES: Map<&str, u64> = Map::new("likes");
-pub const USER_LIKES: Map<(&Addr, &str), bool> = Map::new("user_likes");
+/// Posts with an expiry keyed by (hub, expires at, post sequence), soonest first.
+pub const EXPIRING_POSTS: Map<(&str, u64, u64), bool> = Map::new("expiring_posts");
+/// Posts keyed by (hub, published at, post sequence); the feed reads it newest first and the
+/// scheduled queue is whatever lies in the future.
+pub const HUB_FEED: Map<(&str, u64, u64), bool> = Map::new("hub_feed");
+
+/// Option index each user voted for, keyed by (hub, post id, voter).
+pub const POLL_VOTES: Map<(&str, &str, &Addr), u32> = Map::new("poll_votes");
+/// Vote counts keyed by (hub, post id, option index).
+pub const POLL_TALLIES: Map<(&str, &str, u32), u64> = Map::new("poll_tallies");
+
+/// An ordered collection of a hub's posts, e.g. the parts of a long report.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Series {
+    pub id: u64,
+    pub title: String,
+    pub post_ids: Vec<String>,
+}
+
+/// Series keyed by (hub, series id).
+pub const SERIES: Map<(&str, u64), Series> = Map::new("series");
+/// Next series id per hub.
+pub const SERIES_SEQ: Map<&str, u64> = Map::new("series_seq");
+
+/// Sequences of a hub's pinned posts, in the order they were pinned.
+pub const PINNED_POSTS: Map<&str, Vec<u64>> = Map::new("pinned_posts");
+/// Repost counts keyed by (hub, post id) of the original.
+pub const REPOST_COUNTS: Map<(&str, &str), u64> = Map::new("repost_counts");
+/// Addresses a hub's creator allowed to publish into it, keyed by (hub, author).
+pub const AUTHORS: Map<(&str, &Addr), bool> = Map::new("hub_authors");
+/// Like counts keyed by (hub, post id).
+pub const LIKES: Map<(&str, &str), u64> = Map::new("hub_likes");
+/// Keyed by (user, hub, post id).
+pub const USER_LIKES: Map<(&Addr, &str, &str), bool> = Map::new("hub_user_likes");
+/// Reverse of `USER_LIKES`, so a post's likes can be cleaned up without a full scan.
+pub const POST_LIKERS: Map<(&str, &str, &Addr), bool> = Map::new("hub_post_likers");
+
+/// Voluntary contributions above the minimum price, keyed by (hub, subscriber).
+pub const CONTRIBUTIONS: Map<(&str, &Addr), Uint128> = Map::new("contributions");
+/// Keyed by (hub, total contributed, subscriber), so supporters can be paged by amount.
+pub const SUPPORTERS: Map<(&str, u128, &Addr), bool> = Map::new("supporters_by_amount");
+
+pub const SPONSOR_POOLS: Map<u64, SponsorPool> = Map::new("sponsor_pools");
+pub const SPONSOR_POOL_COUNT: Item<u64> = Item::new("sponsor_pool_count");
+
+/// X25519 public keys users registered to receive wrapped content keys.
+pub const ENCRYPTION_KEYS: Map<&Addr, Binary> = Map::new("encryption_keys");
+/// Content keys of encrypted posts wrapped per subscriber, keyed by (hub, post id, subscriber).
+pub const POST_KEYS: Map<(&str, &str, &Addr), Binary> = Map::new("post_keys");
+
+/// Tagged posts keyed by (tag, hub, post sequence).
+pub const TAGGED_POSTS: Map<(&str, &str, u64), bool> = Map::new("tagged_posts");
+/// Number of published posts per (hub, tag), kept while any post of the hub has the tag.
+/// Scheduled and expired posts are still counted; queries discount them.
+pub const HUB_TAGS: Map<(&str, &str), u64> = Map::new("hub_tags");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub admin: Addr,
+    pub limits: Limits,
+}
+
+/// Inclusive bounds on a text field's length in characters.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LengthLimit {
+    pub min: u32,
+    pub max: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Limits {
+    pub hub_name: LengthLimit,
+    pub title: LengthLimit,
+    pub summary: LengthLimit,
+    pub content: LengthLimit,
+    pub comment: LengthLimit,
+    /// Reject control characters in text fields; content may still contain line breaks and tabs.
+    pub forbid_control_chars: bool,
+    pub max_posts_per_hub: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            hub_name: LengthLimit { min: 1, max: 64 },
+            title: LengthLimit { min: 1, max: 200 },
+            summary: LengthLimit { min: 0, max: 500 },
+            // Off-chain and encrypted posts may leave the body empty
+            content: LengthLimit {
+                min: 0,
+                max: 20_000,
+            },
+            comment: LengthLimit { min: 1, max: 2000 },
+            forbid_control_chars: true,
+            max_posts_per_hub: 10_000,
+        }
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// Number of stored posts per hub, scheduled and unpublished ones included.
+pub const POST_COUNTS: Map<&str, u64> = Map::new("post_counts");